@@ -1,5 +1,8 @@
+use crate::User;
 use crate::error::AreiaResult;
-use crate::utils::{create_all_dir_with_file, delete_all_dir_with_files};
+use crate::utils::{
+    create_all_dir_with_file, create_all_dir_with_file_for, delete_all_dir_with_files,
+};
 use std::path::PathBuf;
 
 /// Convenience function to create a directory with a file in it.
@@ -25,6 +28,39 @@ pub fn auto_creator<P: Into<PathBuf>>(path: P) -> AreiaResult<()> {
     create_all_dir_with_file(&path)
 }
 
+/// Like `auto_creator()`, but the created file and directories are owned by `user`.
+///
+/// Meant for tools running through `sudo` or `pkexec`, so the files they create for the invoking
+/// user do not end up owned by root.
+/// Parent directories that already existed keep their owner.
+/// Unlike `auto_creator()`, an existing file is left alone: it is neither truncated nor handed
+/// over, and a symlink in place of the file is an error, so nothing the user planted is followed.
+///
+/// | Linux | Windows | macOS |
+/// | --- | --- | --- |
+/// | Supported | Error | Supported |
+///
+/// # Example
+///
+/// ```no_run
+/// use areia::{BaseDirs, User, auto_creator_for};
+///
+/// if let Some(user) = User::invoking() {
+///     let base = BaseDirs::for_user(user.clone()).unwrap();
+///     auto_creator_for(base.config_dir().join("app/settings.toml"), &user).unwrap();
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Errors if the directory cannot be created or its owner cannot be changed and returns the OS
+/// error, `AreiaError::UserNotFound` if the user does not exist and
+/// `AreiaError::OtherUsersNotSupported` on Windows.
+pub fn auto_creator_for<P: Into<PathBuf>>(path: P, user: &User) -> AreiaResult<()> {
+    let path = path.into();
+    create_all_dir_with_file_for(&path, user)
+}
+
 /// Convenience function to delete a directory with files in it.
 ///
 /// ! USE WITH CAUTION !
@@ -0,0 +1,96 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::{BaseDirs, UserDirs, error::AreiaResult};
+
+static BASE_DIRS: RwLock<Option<Arc<BaseDirs>>> = RwLock::new(None);
+static USER_DIRS: RwLock<Option<Arc<UserDirs>>> = RwLock::new(None);
+
+/// Returns the cached value of `cache`, resolving it with `resolve` on first use
+///
+/// Errors are not cached, the next call tries again.
+fn cached<T>(
+    cache: &RwLock<Option<Arc<T>>>,
+    resolve: fn() -> AreiaResult<T>,
+) -> AreiaResult<Arc<T>> {
+    // A panic while holding the lock can't leave the `Option` half written
+    if let Some(value) = cache
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        return Ok(Arc::clone(value));
+    }
+    let mut cache = cache.write().unwrap_or_else(PoisonError::into_inner);
+    // Another thread may have resolved it while waiting for the lock
+    if let Some(value) = cache.as_ref() {
+        return Ok(Arc::clone(value));
+    }
+    let value = Arc::new(resolve()?);
+    *cache = Some(Arc::clone(&value));
+    Ok(value)
+}
+
+/// The base directories of the current process, resolved once and cached
+///
+/// The first call resolves them with `BaseDirs::new()`, later calls return the cached value
+/// until `refresh()` is called.
+/// Safe to call from multiple threads.
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, base_dirs};
+///
+/// let base = base_dirs().unwrap();
+/// assert_eq!(base.home_dir(), BaseDirs::new().unwrap().home_dir());
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of `BaseDirs::new()`, errors are not cached
+pub fn base_dirs() -> AreiaResult<Arc<BaseDirs>> {
+    cached(&BASE_DIRS, BaseDirs::new)
+}
+
+/// The user directories of the current process, resolved once and cached
+///
+/// The first call resolves them with `UserDirs::new()`, later calls return the cached value
+/// until `refresh()` is called.
+/// Safe to call from multiple threads.
+///
+/// # Example
+///
+/// ```
+/// use areia::{UserDirs, user_dirs};
+///
+/// let user = user_dirs().unwrap();
+/// assert_eq!(user.home_dir(), UserDirs::new().unwrap().home_dir());
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of `UserDirs::new()`, errors are not cached
+pub fn user_dirs() -> AreiaResult<Arc<UserDirs>> {
+    cached(&USER_DIRS, UserDirs::new)
+}
+
+/// Drops the cached directories of `base_dirs()` and `user_dirs()`
+///
+/// The next call resolves them again.
+/// Long-running processes call this after the environment or `user-dirs.dirs` changed.
+/// Values returned before stay valid, but are not updated.
+///
+/// # Example
+///
+/// ```
+/// use areia::{base_dirs, refresh};
+///
+/// let before = base_dirs().unwrap();
+/// refresh();
+/// let after = base_dirs().unwrap();
+/// assert_eq!(before.home_dir(), after.home_dir());
+/// ```
+pub fn refresh() {
+    *BASE_DIRS.write().unwrap_or_else(PoisonError::into_inner) = None;
+    *USER_DIRS.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
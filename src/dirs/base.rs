@@ -1,16 +1,35 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 use crate::{
-    error::AreiaResult,
+    DirKind, DirSource, EnvSource, Excludes, Explanation, ProcessEnv, Sandbox, User,
+    dirs::{portable::portable_root, sandbox::FLATPAK_INFO},
+    env::PasswdEnv,
+    error::{AreiaError, AreiaResult},
+    mark_excluded,
     utils::{
+        create_private_dir_all, create_runtime_fallback,
         factory::{
-            cache_dir, config_dir, config_local_dir, data_dir, data_local_dir, executable_dir,
-            preference_dir, runtime_dir, state_dir,
+            base_dir_source, cache_dir, config_dir, config_dirs, config_local_dir, data_dir,
+            data_dirs, data_local_dir, executable_dir, preference_dir, runtime_dir, state_dir,
         },
-        get_home,
+        runtime_dir_violations,
     },
 };
 
+/// The kinds of the base directories, in the order of `DirKind::ALL`
+const BASE_DIRS: [DirKind; 10] = [
+    DirKind::Home,
+    DirKind::Cache,
+    DirKind::Config,
+    DirKind::ConfigLocal,
+    DirKind::Data,
+    DirKind::DataLocal,
+    DirKind::Executable,
+    DirKind::Preference,
+    DirKind::Runtime,
+    DirKind::State,
+];
+
 /// Base directories
 ///
 /// This struct contains all the base directories:
@@ -26,6 +45,9 @@ use crate::{
 /// - `runtime_dir` (If available)
 /// - `state_dir` (If available)
 ///
+/// Additionally the ordered search paths for data and config files are available with
+/// `data_dirs` and `config_dirs`.
+///
 /// Create with `BaseDirs::new()`.
 /// Get paths by using the provided functions on `BaseDirs`.
 #[derive(Debug, Clone)]
@@ -40,12 +62,20 @@ pub struct BaseDirs {
     preference_dir: PathBuf,
     runtime_dir: Option<PathBuf>,
     state_dir: Option<PathBuf>,
+    data_dirs: Vec<PathBuf>,
+    config_dirs: Vec<PathBuf>,
+    explanations: Vec<Explanation>,
+    sandbox: Option<Sandbox>,
+    portable_root: Option<PathBuf>,
 }
 
 impl BaseDirs {
     /// Create a new `BaseDirs`.
     /// All paths will be constructed based on the current user.
     ///
+    /// The paths are returned even if they do not exist yet, e.g. on a fresh user account.
+    /// Use `BaseDirs::ensure()` or `BaseDirs::ensure_dir()` to create them.
+    ///
     /// Returns an `AreiaResult` containing the `BaseDirs`
     ///
     /// # Example
@@ -62,7 +92,172 @@ impl BaseDirs {
     ///
     /// Returns an `AreiaError` if the home directory could not be found or an OS error occurred
     pub fn new() -> AreiaResult<BaseDirs> {
-        base_dirs()
+        base_dirs(&ProcessEnv, true)
+    }
+
+    /// Create a new `BaseDirs` from a caller supplied environment.
+    /// All paths will be constructed based on the environment variables and home directory of
+    /// `env`, the environment of the process is not read.
+    ///
+    /// On Windows the base directories are known folders of the current user, only the
+    /// `home_dir` is taken from `env`.
+    ///
+    /// Returns an `AreiaResult` containing the `BaseDirs`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, MapEnv};
+    /// use std::path::PathBuf;
+    ///
+    /// let env = MapEnv::new("/home/alice").with_var("XDG_CONFIG_HOME", "/srv/alice/config");
+    /// let base = BaseDirs::from_env(&env).unwrap();
+    /// assert_eq!(base.home_dir(), &PathBuf::from("/home/alice"));
+    /// if cfg!(target_os = "linux") {
+    ///     assert_eq!(base.config_dir(), &PathBuf::from("/srv/alice/config"));
+    ///     assert_eq!(base.cache_dir(), &PathBuf::from("/home/alice/.cache"));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if `env` provides no home directory or an OS error occurred
+    pub fn from_env(env: &dyn EnvSource) -> AreiaResult<BaseDirs> {
+        base_dirs(env, false)
+    }
+
+    /// Create a new `BaseDirs` for another user, addressed by name or uid.
+    /// The home directory is read from the passwd database.
+    ///
+    /// The environment variables of the other user are unknown, so all directories are the
+    /// defaults inside their home directory, e.g. `$HOME/.config` on Linux.
+    /// Reading or writing them usually requires elevated privileges.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | Supported | Error | Supported |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::BaseDirs;
+    ///
+    /// if cfg!(unix) {
+    ///     let root = BaseDirs::for_user("root").unwrap();
+    ///     assert!(root.home_dir().is_absolute());
+    ///     assert_eq!(BaseDirs::for_user(0).unwrap().home_dir(), root.home_dir());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the user does not exist and
+    /// `AreiaError::OtherUsersNotSupported` on Windows.
+    pub fn for_user<U: Into<User>>(user: U) -> AreiaResult<BaseDirs> {
+        base_dirs(&PasswdEnv::new(&user.into())?, false)
+    }
+
+    /// Create a new `BaseDirs` for the user who invoked the process through `sudo` or `pkexec`.
+    ///
+    /// Without `sudo` or `pkexec` this is the same as `BaseDirs::new()`, otherwise the same as
+    /// `BaseDirs::for_user()` with the user detected by `User::invoking()`.
+    /// This keeps tools run with elevated privileges from writing into the home directory of
+    /// root.
+    /// Files created for the invoking user can be handed over with `User::chown()` or created
+    /// with `auto_creator_for()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, User};
+    ///
+    /// let dirs = BaseDirs::for_invoking_user().unwrap();
+    /// if User::invoking().is_none() {
+    ///     assert_eq!(dirs.home_dir(), BaseDirs::new().unwrap().home_dir());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the invoking user does not exist or any error of
+    /// `BaseDirs::new()`
+    pub fn for_invoking_user() -> AreiaResult<BaseDirs> {
+        match User::invoking() {
+            Some(user) => base_dirs(&PasswdEnv::new(&user)?, false),
+            None => base_dirs(&ProcessEnv, true),
+        }
+    }
+
+    /// Create a new `BaseDirs` in portable mode, with all directories inside `root`.
+    ///
+    /// The real home directory is never used, so a tool can run from a USB stick or a
+    /// self-contained bundle without leaving traces.
+    /// Inside `root` the layout of the XDG defaults is kept:
+    ///
+    /// | Directory | Path |
+    /// | --- | --- |
+    /// | `home_dir` | `{root}` |
+    /// | `cache_dir` | `{root}/cache` |
+    /// | `config_dir`, `config_local_dir`, `preference_dir` | `{root}/config` |
+    /// | `data_dir`, `data_local_dir` | `{root}/data` |
+    /// | `executable_dir` | `{root}/bin` |
+    /// | `state_dir` | `{root}/state` |
+    /// | `runtime_dir` | None, see `BaseDirs::runtime_dir_or_fallback()` |
+    ///
+    /// The search paths only contain the directories inside `root`.
+    ///
+    /// Portable mode is also enabled by `BaseDirs::new()` and `BaseDirs::from_env()` if
+    /// `$AREIA_PORTABLE_ROOT` is set to an absolute path, and by `BaseDirs::new()` if a file
+    /// called `areia-portable` is next to the executable.
+    /// The first non-empty line of that file is the root relative to the executable, an empty
+    /// file makes the directory of the executable the root.
+    /// The file is looked for once per process, later calls of `BaseDirs::new()` reuse the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::BaseDirs;
+    /// use std::path::PathBuf;
+    ///
+    /// if cfg!(unix) {
+    ///     let base = BaseDirs::portable("/media/usb/app").unwrap();
+    ///     assert_eq!(base.portable_root(), Some(&PathBuf::from("/media/usb/app")));
+    ///     assert_eq!(base.config_dir(), &PathBuf::from("/media/usb/app/config"));
+    ///     assert!(base.runtime_dir().is_none());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeAbsolute` if `root` is relative
+    pub fn portable<P: Into<PathBuf>>(root: P) -> AreiaResult<BaseDirs> {
+        let root = root.into();
+        if !root.is_absolute() {
+            return Err(AreiaError::PathMustBeAbsolute(root));
+        }
+        Ok(portable_base_dirs(root))
+    }
+
+    /// Get the root of portable mode
+    ///
+    /// Returns `None` if portable mode is not enabled, see `BaseDirs::portable()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, MapEnv};
+    /// use std::path::PathBuf;
+    ///
+    /// if cfg!(unix) {
+    ///     let env = MapEnv::new("/home/alice").with_var("AREIA_PORTABLE_ROOT", "/opt/bundle");
+    ///     let base = BaseDirs::from_env(&env).unwrap();
+    ///     assert_eq!(base.portable_root(), Some(&PathBuf::from("/opt/bundle")));
+    ///     assert_eq!(base.home_dir(), &PathBuf::from("/opt/bundle"));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn portable_root(&self) -> Option<&PathBuf> {
+        self.portable_root.as_ref()
     }
 
     /// Get the home directory
@@ -238,6 +433,81 @@ impl BaseDirs {
         self.runtime_dir.as_ref()
     }
 
+    /// Get the runtime directory, if it fulfills the requirements of the XDG spec
+    ///
+    /// The directory must be owned by the user, only be accessible by the user (mode `0700`) and
+    /// be on a local filesystem.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, error::AreiaError};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// match base.validate_runtime_dir() {
+    ///     Ok(runtime) => assert_eq!(Some(runtime), base.runtime_dir()),
+    ///     Err(AreiaError::InvalidRuntimeDir(path, violations)) => {
+    ///         println!("Insecure runtime directory {path:?}: {violations:?}");
+    ///     }
+    ///     Err(err) => println!("{err}"),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::DirectoryNotAvailable` if there is no runtime directory and
+    /// `AreiaError::InvalidRuntimeDir` with every violated requirement otherwise.
+    pub fn validate_runtime_dir(&self) -> AreiaResult<&PathBuf> {
+        let Some(runtime_dir) = self.runtime_dir() else {
+            return Err(AreiaError::DirectoryNotAvailable(DirKind::Runtime));
+        };
+        let violations = runtime_dir_violations(runtime_dir);
+        if violations.is_empty() {
+            Ok(runtime_dir)
+        } else {
+            Err(AreiaError::InvalidRuntimeDir(
+                runtime_dir.clone(),
+                violations,
+            ))
+        }
+    }
+
+    /// Get the validated runtime directory, or a private fallback if it is not set
+    ///
+    /// `$XDG_RUNTIME_DIR` is often unset, e.g. under cron, ssh sessions without `systemd-logind`
+    /// or in containers.
+    /// In that case the directory `runtime-{uid}` is created inside the temporary directory
+    /// (usually `/tmp`) with the mode `0700`.
+    /// An existing fallback directory is only used if it is owned by the user and not a symlink.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_RUNTIME_DIR` OR `/tmp/runtime-{uid}` | Error | `$TMPDIR/runtime-{uid}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::BaseDirs;
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// if let Ok(runtime) = base.runtime_dir_or_fallback() {
+    ///     assert!(runtime.is_dir());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::InvalidRuntimeDir` if the set runtime directory or the fallback
+    /// violates the requirements of the XDG spec, see `BaseDirs::validate_runtime_dir()`.
+    /// Returns `AreiaError::DirectoryNotAvailable` on Windows.
+    pub fn runtime_dir_or_fallback(&self) -> AreiaResult<PathBuf> {
+        match self.validate_runtime_dir() {
+            Ok(runtime_dir) => Ok(runtime_dir.clone()),
+            Err(AreiaError::DirectoryNotAvailable(_)) => create_runtime_fallback(),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Get the state directory
     ///
     /// | Linux | Windows | macOS |
@@ -260,21 +530,445 @@ impl BaseDirs {
     pub fn state_dir(&self) -> Option<&PathBuf> {
         self.state_dir.as_ref()
     }
+
+    /// Get the data search path
+    ///
+    /// Yields the `data_dir` first, followed by the system wide data directories in order of
+    /// precedence.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `data_dir`, `$XDG_DATA_DIRS` OR `/usr/local/share:/usr/share` | `data_dir`, `{FolderID::ProgramData}` | `data_dir`, `/Library/Application Support` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::BaseDirs;
+    /// let base = BaseDirs::new().unwrap();
+    /// let mut data_dirs = base.data_dirs();
+    /// assert_eq!(data_dirs.next(), Some(base.data_dir()));
+    /// assert!(data_dirs.all(|dir| dir.is_absolute()));
+    /// ```
+    pub fn data_dirs(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.data_dir).chain(self.data_dirs.iter())
+    }
+
+    /// Get the config search path
+    ///
+    /// Yields the `config_dir` first, followed by the system wide config directories in order of
+    /// precedence.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `config_dir`, `$XDG_CONFIG_DIRS` OR `/etc/xdg` | `config_dir`, `{FolderID::ProgramData}` | `config_dir`, `/Library/Application Support` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::BaseDirs;
+    /// let base = BaseDirs::new().unwrap();
+    /// let mut config_dirs = base.config_dirs();
+    /// assert_eq!(config_dirs.next(), Some(base.config_dir()));
+    /// assert!(config_dirs.all(|dir| dir.is_absolute()));
+    /// ```
+    pub fn config_dirs(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.config_dir).chain(self.config_dirs.iter())
+    }
+
+    /// Find a data file
+    ///
+    /// Searches the `data_dirs` in order of precedence for the relative `path`.
+    /// Returns the first existing match, so files of the user win over system wide files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::BaseDirs;
+    /// let base = BaseDirs::new().unwrap();
+    /// let icon = base.find_data_file("my-app/icon.png").unwrap();
+    /// if let Some(icon) = icon {
+    ///     assert!(icon.exists());
+    /// }
+    /// assert!(base.find_data_file("../icon.png").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeSomething` if `path` is empty or not a plain relative path
+    pub fn find_data_file<P: AsRef<Path>>(&self, path: P) -> AreiaResult<Option<PathBuf>> {
+        Ok(find_files(self.data_dirs(), path.as_ref())?.next())
+    }
+
+    /// Find a config file
+    ///
+    /// Searches the `config_dirs` in order of precedence for the relative `path`.
+    /// Returns the first existing match, so files of the user win over system wide files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::BaseDirs;
+    /// let base = BaseDirs::new().unwrap();
+    /// let config = base.find_config_file("my-app/config.toml").unwrap();
+    /// if let Some(config) = config {
+    ///     assert!(config.exists());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeSomething` if `path` is empty or not a plain relative path
+    pub fn find_config_file<P: AsRef<Path>>(&self, path: P) -> AreiaResult<Option<PathBuf>> {
+        Ok(find_files(self.config_dirs(), path.as_ref())?.next())
+    }
+
+    /// Find all config files
+    ///
+    /// Searches the `config_dirs` for the relative `path`.
+    /// Returns every existing match, ordered from highest to lowest precedence.
+    /// Useful for merging configs, where the user config overrides the system wide defaults.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::BaseDirs;
+    /// let base = BaseDirs::new().unwrap();
+    /// let configs = base.find_all_config_files("my-app/config.toml").unwrap();
+    /// assert!(configs.iter().all(|config| config.exists()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeSomething` if `path` is empty or not a plain relative path
+    pub fn find_all_config_files<P: AsRef<Path>>(&self, path: P) -> AreiaResult<Vec<PathBuf>> {
+        Ok(find_files(self.config_dirs(), path.as_ref())?.collect())
+    }
+}
+
+impl BaseDirs {
+    /// Create all missing base directories
+    ///
+    /// Every directory of this `BaseDirs` that does not exist yet is created, including missing
+    /// parents, with the permission `0700` on Unix.
+    /// The `runtime_dir` is managed by the system and is never created by this function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::BaseDirs;
+    /// let base = BaseDirs::new().unwrap();
+    /// assert!(base.ensure().is_ok());
+    /// assert!(base.cache_dir().is_dir());
+    /// assert!(base.config_dir().is_dir());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if any directory could not be created
+    pub fn ensure(&self) -> AreiaResult<()> {
+        for kind in [
+            DirKind::Cache,
+            DirKind::Config,
+            DirKind::ConfigLocal,
+            DirKind::Data,
+            DirKind::DataLocal,
+            DirKind::Executable,
+            DirKind::Preference,
+            DirKind::State,
+        ] {
+            if self.dir(kind).is_some() {
+                self.ensure_dir(kind)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a single base directory if it is missing
+    ///
+    /// The directory and any missing parents are created with the permission `0700` on Unix.
+    /// The runtime directory is set up by the login manager, so it is never created, see
+    /// `BaseDirs::runtime_dir_or_fallback()` instead.
+    ///
+    /// Returns the path of the directory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, DirKind};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let cache = base.ensure_dir(DirKind::Cache).unwrap();
+    /// assert!(cache.is_dir());
+    /// assert!(base.ensure_dir(DirKind::Runtime).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::DirectoryManagedBySystem` for `DirKind::Runtime`,
+    /// `AreiaError::DirectoryNotAvailable` if the directory does not exist on this OS
+    /// (e.g. `state_dir` on Windows), or an `AreiaError` if it could not be created
+    pub fn ensure_dir(&self, kind: DirKind) -> AreiaResult<PathBuf> {
+        if kind == DirKind::Runtime {
+            return Err(AreiaError::DirectoryManagedBySystem(kind));
+        }
+        let Some(dir) = self.dir(kind) else {
+            return Err(AreiaError::DirectoryNotAvailable(kind));
+        };
+        if !dir.is_dir() {
+            create_private_dir_all(dir)?;
+        }
+        Ok(dir.clone())
+    }
+
+    /// Create the cache directory of an application, excluded from backups, indexing and media
+    /// scanners
+    ///
+    /// `app` is a relative path inside `cache_dir()`, e.g. `ProjectDirs::project_path()`.
+    /// The directory is created like `ensure_dir()` does and marked with
+    /// `mark_excluded(dir, Excludes::all())`, see `Exclude` for the marker files.
+    /// `cache_dir()` itself is left alone, other applications may want their caches backed up.
+    ///
+    /// Returns the path of the directory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, Exclude, excluded_from};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let cache = base.ensure_app_cache_dir("areia-doc-app").unwrap();
+    /// assert!(cache.is_dir());
+    /// assert!(excluded_from(cache.join("blobs")).contains(Exclude::Backup));
+    /// # std::fs::remove_dir_all(&cache).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeSomething` if `app` is empty or not a plain relative path,
+    /// or an `AreiaError` if the directory or a marker could not be written
+    pub fn ensure_app_cache_dir<P: AsRef<Path>>(&self, app: P) -> AreiaResult<PathBuf> {
+        let app = plain_relative_path(app.as_ref())?;
+        let dir = self.cache_dir.join(app);
+        mark_excluded(&dir, Excludes::all())?;
+        Ok(dir)
+    }
+
+    /// Explain where every base directory comes from
+    ///
+    /// There is one record for each base directory, including the home directory and
+    /// directories that are not available on this OS.
+    /// Each record contains the resolved path, its source (e.g. the environment variable it was
+    /// read from) and the value of an environment variable that was ignored, because it is
+    /// relative.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `EnvVar` OR `Default`, the home directory `EnvVar("HOME")` OR `Passwd` | `Default` | `Default`, the home directory `EnvVar("HOME")` OR `Passwd` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::BaseDirs;
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// for record in base.explain() {
+    ///     println!("{record}");
+    /// }
+    /// assert_eq!(base.explain().len(), 10);
+    /// ```
+    #[must_use]
+    pub fn explain(&self) -> &[Explanation] {
+        &self.explanations
+    }
+
+    /// Get the sandbox the application runs in
+    ///
+    /// Returns `None` outside of a sandbox.
+    /// Flatpak is detected by `/.flatpak-info` or `$FLATPAK_ID`, Snap by `$SNAP`.
+    /// For `BaseDirs::from_env()` only the environment variables are checked.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | Flatpak, Snap | None | None |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, MapEnv, SandboxKind};
+    ///
+    /// let env = MapEnv::new("/home/alice")
+    ///     .with_var("FLATPAK_ID", "org.example.App")
+    ///     .with_var("XDG_CONFIG_HOME", "/home/alice/.var/app/org.example.App/config");
+    /// let base = BaseDirs::from_env(&env).unwrap();
+    /// if cfg!(target_os = "linux") {
+    ///     let sandbox = base.sandbox().unwrap();
+    ///     assert_eq!(sandbox.kind(), SandboxKind::Flatpak);
+    ///     assert_eq!(sandbox.app_id(), Some("org.example.App"));
+    ///     assert!(base.config_dir().ends_with("org.example.App/config"));
+    ///     assert!(sandbox.host_dirs().config_dir().ends_with("alice/.config"));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn sandbox(&self) -> Option<&Sandbox> {
+        self.sandbox.as_ref()
+    }
+
+    /// Get the base directories of the host
+    ///
+    /// Inside a sandbox these are the directories outside of it, see `Sandbox::host_dirs()`,
+    /// otherwise `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::BaseDirs;
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// if base.sandbox().is_none() {
+    ///     assert_eq!(base.host_dirs().config_dir(), base.config_dir());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn host_dirs(&self) -> &BaseDirs {
+        self.sandbox.as_ref().map_or(self, Sandbox::host_dirs)
+    }
+
+    /// Get the source a base directory was resolved from
+    ///
+    /// Returns `None` if `kind` is not a base directory, see `BaseDirs::explain()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, DirKind, DirSource, MapEnv};
+    ///
+    /// let env = MapEnv::new("/home/alice").with_var("XDG_CACHE_HOME", "/srv/cache");
+    /// let base = BaseDirs::from_env(&env).unwrap();
+    /// assert_eq!(base.source(DirKind::Home), Some(&DirSource::Supplied));
+    /// if cfg!(target_os = "linux") {
+    ///     let var = DirSource::EnvVar("XDG_CACHE_HOME".to_owned());
+    ///     assert_eq!(base.source(DirKind::Cache), Some(&var));
+    /// }
+    /// assert!(base.source(DirKind::Desktop).is_none());
+    /// ```
+    #[must_use]
+    pub fn source(&self, kind: DirKind) -> Option<&DirSource> {
+        self.explanations
+            .iter()
+            .find(|record| record.kind() == kind)
+            .map(Explanation::source)
+    }
+
+    /// Get a directory by its kind
+    ///
+    /// Returns `None` if the directory is not available on this OS or `kind` is a user directory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, DirKind};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// assert_eq!(base.get(DirKind::Config), Some(base.config_dir().as_path()));
+    /// let kind: DirKind = "data-local".parse().unwrap();
+    /// assert_eq!(base.get(kind), Some(base.data_local_dir().as_path()));
+    /// assert!(base.get(DirKind::Desktop).is_none());
+    /// ```
+    #[must_use]
+    pub fn get(&self, kind: DirKind) -> Option<&Path> {
+        self.dir(kind).map(PathBuf::as_path)
+    }
+
+    /// Iterate over every available directory, including the home directory
+    ///
+    /// The directories are yielded in the order of `DirKind::ALL`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, DirKind};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// for (kind, path) in base.iter() {
+    ///     assert_eq!(base.get(kind), Some(path));
+    /// }
+    /// assert!(base.iter().any(|(kind, _)| kind == DirKind::Cache));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (DirKind, &Path)> {
+        DirKind::ALL
+            .into_iter()
+            .filter_map(|kind| Some((kind, self.get(kind)?)))
+    }
+
+    fn dir(&self, kind: DirKind) -> Option<&PathBuf> {
+        match kind {
+            DirKind::Home => Some(self.home_dir()),
+            DirKind::Cache => Some(self.cache_dir()),
+            DirKind::Config => Some(self.config_dir()),
+            DirKind::ConfigLocal => Some(self.config_local_dir()),
+            DirKind::Data => Some(self.data_dir()),
+            DirKind::DataLocal => Some(self.data_local_dir()),
+            DirKind::Executable => self.executable_dir(),
+            DirKind::Preference => Some(self.preference_dir()),
+            DirKind::Runtime => self.runtime_dir(),
+            DirKind::State => self.state_dir(),
+            DirKind::Audio
+            | DirKind::Desktop
+            | DirKind::Document
+            | DirKind::Download
+            | DirKind::Font
+            | DirKind::Picture
+            | DirKind::Public
+            | DirKind::Template
+            | DirKind::Video
+            | DirKind::Screenshot
+            | DirKind::Project => None,
+        }
+    }
 }
 
-fn base_dirs() -> AreiaResult<BaseDirs> {
-    match get_home() {
+/// A path inside a base directory must not be empty or escape it, e.g. by `..` or a root
+fn plain_relative_path(path: &Path) -> AreiaResult<&Path> {
+    if path.as_os_str().is_empty()
+        || path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(AreiaError::PathMustBeSomething(path.to_path_buf()));
+    }
+    Ok(path)
+}
+
+fn find_files<'a>(
+    dirs: impl Iterator<Item = &'a PathBuf> + 'a,
+    path: &'a Path,
+) -> AreiaResult<impl Iterator<Item = PathBuf> + 'a> {
+    let path = plain_relative_path(path)?;
+    Ok(dirs
+        .map(move |dir| dir.join(path))
+        .filter(|candidate| candidate.exists()))
+}
+
+/// `probe_files` enables the detection of Flatpak and portable mode by files, which only
+/// applies to the environment of the process
+fn base_dirs(env: &dyn EnvSource, probe_files: bool) -> AreiaResult<BaseDirs> {
+    if let Some(root) = portable_root(env, probe_files) {
+        return Ok(portable_base_dirs(root));
+    }
+    match env.home_dir() {
         Ok(home_dir) => {
-            let cache_dir = cache_dir(home_dir.clone());
-            let config_dir = config_dir(home_dir.clone());
-            let config_local_dir = config_local_dir(home_dir.clone());
-            let data_dir = data_dir(home_dir.clone());
-            let data_local_dir = data_local_dir(home_dir.clone());
-            let preference_dir = preference_dir(home_dir.clone());
-            let runtime_dir = runtime_dir();
-            let state_dir = state_dir(home_dir.clone());
-            let executable_dir = executable_dir(home_dir.clone());
-            Ok(BaseDirs {
+            let cache_dir = cache_dir(env, home_dir.clone());
+            let config_dir = config_dir(env, home_dir.clone());
+            let config_local_dir = config_local_dir(env, home_dir.clone());
+            let data_dir = data_dir(env, home_dir.clone());
+            let data_local_dir = data_local_dir(env, home_dir.clone());
+            let preference_dir = preference_dir(env, home_dir.clone());
+            let runtime_dir = runtime_dir(env);
+            let state_dir = state_dir(env, home_dir.clone());
+            let executable_dir = executable_dir(env, home_dir.clone());
+            let data_dirs = data_dirs(env);
+            let config_dirs = config_dirs(env);
+            let mut base = BaseDirs {
                 home_dir,
                 cache_dir,
                 config_dir,
@@ -285,8 +979,56 @@ fn base_dirs() -> AreiaResult<BaseDirs> {
                 preference_dir,
                 runtime_dir,
                 state_dir,
-            })
+                data_dirs,
+                config_dirs,
+                explanations: Vec::new(),
+                portable_root: None,
+                sandbox: Sandbox::detect(env, probe_files.then(|| Path::new(FLATPAK_INFO))),
+            };
+            base.explanations = BASE_DIRS
+                .into_iter()
+                .map(|kind| {
+                    let (source, rejected) = if kind == DirKind::Home {
+                        (env.home_dir_source(), None)
+                    } else {
+                        base_dir_source(env, kind)
+                    };
+                    let path = base.get(kind).map(Path::to_path_buf);
+                    Explanation::new(kind, path, source, rejected)
+                })
+                .collect();
+            Ok(base)
         }
         Err(err) => Err(err),
     }
 }
+
+/// All directories inside `root`, in the layout of the XDG defaults
+fn portable_base_dirs(root: PathBuf) -> BaseDirs {
+    let mut base = BaseDirs {
+        home_dir: root.clone(),
+        cache_dir: root.join("cache"),
+        config_dir: root.join("config"),
+        config_local_dir: root.join("config"),
+        data_dir: root.join("data"),
+        data_local_dir: root.join("data"),
+        executable_dir: Some(root.join("bin")),
+        preference_dir: root.join("config"),
+        runtime_dir: None,
+        state_dir: Some(root.join("state")),
+        data_dirs: Vec::new(),
+        config_dirs: Vec::new(),
+        explanations: Vec::new(),
+        sandbox: None,
+        portable_root: None,
+    };
+    base.explanations = BASE_DIRS
+        .into_iter()
+        .map(|kind| {
+            let path = base.get(kind).map(Path::to_path_buf);
+            Explanation::new(kind, path, DirSource::Portable(root.clone()), None)
+        })
+        .collect();
+    base.portable_root = Some(root);
+    base
+}
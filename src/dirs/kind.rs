@@ -0,0 +1,138 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::AreiaError;
+
+/// The kind of a directory
+///
+/// Used to address a single directory of `BaseDirs` or `UserDirs` generically, e.g. with
+/// `BaseDirs::get()`, `UserDirs::get()` or `BaseDirs::ensure_dir()`.
+///
+/// A kind can be parsed from its name, which is the same as its `Display` output, e.g.
+/// `"config-local"` for `DirKind::ConfigLocal`.
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, DirKind};
+///
+/// let kind: DirKind = "cache".parse().unwrap();
+/// assert_eq!(kind, DirKind::Cache);
+/// assert_eq!(kind.to_string(), "cache");
+///
+/// let base = BaseDirs::new().unwrap();
+/// assert_eq!(base.get(kind), Some(base.cache_dir().as_path()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirKind {
+    Home,
+    Cache,
+    Config,
+    ConfigLocal,
+    Data,
+    DataLocal,
+    Executable,
+    Preference,
+    Runtime,
+    State,
+    Audio,
+    Desktop,
+    Document,
+    Download,
+    Font,
+    Picture,
+    Public,
+    Template,
+    Video,
+    Screenshot,
+    Project,
+}
+
+impl DirKind {
+    /// Every kind, the base directories first, followed by the user directories
+    pub const ALL: [DirKind; 21] = [
+        DirKind::Home,
+        DirKind::Cache,
+        DirKind::Config,
+        DirKind::ConfigLocal,
+        DirKind::Data,
+        DirKind::DataLocal,
+        DirKind::Executable,
+        DirKind::Preference,
+        DirKind::Runtime,
+        DirKind::State,
+        DirKind::Audio,
+        DirKind::Desktop,
+        DirKind::Document,
+        DirKind::Download,
+        DirKind::Font,
+        DirKind::Picture,
+        DirKind::Public,
+        DirKind::Template,
+        DirKind::Video,
+        DirKind::Screenshot,
+        DirKind::Project,
+    ];
+
+    /// The key of a user directory inside `user-dirs.dirs`, without the `XDG_` and `_DIR` affixes
+    pub(crate) fn xdg_key(self) -> Option<&'static str> {
+        match self {
+            DirKind::Audio => Some("MUSIC"),
+            DirKind::Desktop => Some("DESKTOP"),
+            DirKind::Document => Some("DOCUMENTS"),
+            DirKind::Download => Some("DOWNLOAD"),
+            DirKind::Picture => Some("PICTURES"),
+            DirKind::Public => Some("PUBLICSHARE"),
+            DirKind::Template => Some("TEMPLATES"),
+            DirKind::Video => Some("VIDEOS"),
+            DirKind::Screenshot => Some("SCREENSHOTS"),
+            DirKind::Project => Some("PROJECTS"),
+            _ => None,
+        }
+    }
+
+    /// The kind of a `user-dirs.dirs` key, the inverse of `DirKind::xdg_key()`
+    pub(crate) fn from_xdg_key(key: &str) -> Option<DirKind> {
+        DirKind::ALL
+            .into_iter()
+            .find(|kind| kind.xdg_key() == Some(key))
+    }
+}
+
+impl Display for DirKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DirKind::Home => write!(f, "home"),
+            DirKind::Cache => write!(f, "cache"),
+            DirKind::Config => write!(f, "config"),
+            DirKind::ConfigLocal => write!(f, "config-local"),
+            DirKind::Data => write!(f, "data"),
+            DirKind::DataLocal => write!(f, "data-local"),
+            DirKind::Executable => write!(f, "executable"),
+            DirKind::Preference => write!(f, "preference"),
+            DirKind::Runtime => write!(f, "runtime"),
+            DirKind::State => write!(f, "state"),
+            DirKind::Audio => write!(f, "audio"),
+            DirKind::Desktop => write!(f, "desktop"),
+            DirKind::Document => write!(f, "document"),
+            DirKind::Download => write!(f, "download"),
+            DirKind::Font => write!(f, "font"),
+            DirKind::Picture => write!(f, "picture"),
+            DirKind::Public => write!(f, "public"),
+            DirKind::Template => write!(f, "template"),
+            DirKind::Video => write!(f, "video"),
+            DirKind::Screenshot => write!(f, "screenshot"),
+            DirKind::Project => write!(f, "project"),
+        }
+    }
+}
+
+impl FromStr for DirKind {
+    type Err = AreiaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DirKind::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == s)
+            .ok_or_else(|| AreiaError::UnknownDirKind(s.to_owned()))
+    }
+}
@@ -1,5 +1,20 @@
 mod base;
+mod kind;
+mod portable;
+mod project;
+mod runtime;
+mod sandbox;
+mod source;
+mod system;
+mod token;
 mod user;
 
 pub use base::BaseDirs;
+pub use kind::DirKind;
+pub use project::ProjectDirs;
+pub use runtime::RuntimeDirViolation;
+pub use sandbox::{Sandbox, SandboxKind};
+pub use source::{DirSource, Explanation};
+pub use system::SystemDirs;
+pub use token::PathTokens;
 pub use user::UserDirs;
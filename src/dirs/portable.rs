@@ -0,0 +1,48 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::EnvSource;
+
+/// The environment variable that selects the portable root
+pub(crate) const PORTABLE_ROOT_VAR: &str = "AREIA_PORTABLE_ROOT";
+
+/// The marker file next to the executable that enables portable mode
+pub(crate) const PORTABLE_MARKER: &str = "areia-portable";
+
+/// The root of the marker next to the executable, neither of them moves while the process runs
+static MARKER_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Finds the portable root, either in `$AREIA_PORTABLE_ROOT` or - for the environment of the
+/// process - with the marker file next to the executable
+///
+/// A relative `$AREIA_PORTABLE_ROOT` is ignored, like relative XDG variables.
+pub(crate) fn portable_root(env: &dyn EnvSource, probe_marker: bool) -> Option<PathBuf> {
+    if let Some(root) = env.var_os(PORTABLE_ROOT_VAR).map(PathBuf::from)
+        && root.is_absolute()
+    {
+        return Some(root);
+    }
+    if probe_marker {
+        return MARKER_ROOT
+            .get_or_init(|| {
+                let exe = std::env::current_exe().ok()?;
+                marker_root(exe.parent()?)
+            })
+            .clone();
+    }
+    None
+}
+
+/// Reads the marker file in `exe_dir`
+///
+/// The first non-empty line of the marker is the root, relative to `exe_dir`.
+/// An empty marker makes `exe_dir` itself the root.
+fn marker_root(exe_dir: &Path) -> Option<PathBuf> {
+    let marker = std::fs::read_to_string(exe_dir.join(PORTABLE_MARKER)).ok()?;
+    match marker.lines().map(str::trim).find(|line| !line.is_empty()) {
+        Some(root) => Some(exe_dir.join(root)),
+        None => Some(exe_dir.to_path_buf()),
+    }
+}
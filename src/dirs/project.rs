@@ -0,0 +1,356 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::{
+    BaseDirs, DirKind,
+    error::{AreiaError, AreiaResult},
+    utils::factory::{project_path, project_subdir},
+};
+
+/// Project directories
+///
+/// This struct contains all the directories of a single application:
+///
+/// - `project_path`
+/// - `cache_dir`
+/// - `config_dir`
+/// - `config_local_dir`
+/// - `data_dir`
+/// - `data_local_dir`
+/// - `preference_dir`
+/// - `runtime_dir` (If available)
+/// - `state_dir` (If available)
+///
+/// Every directory is the matching `BaseDirs` directory joined with the `project_path`.
+/// On Windows, where the cache, config and data share `LocalAppData` or `RoamingAppData`, the
+/// `cache`, `config` or `data` subdirectory is appended, so wiping the cache keeps the rest.
+/// The `project_path` follows the naming convention of the OS:
+///
+/// | Linux | Windows | macOS |
+/// | --- | --- | --- |
+/// | `my-app` | `Org\App` | `com.Org.App` |
+///
+/// Create with `ProjectDirs::new()`.
+/// Get paths by using the provided functions on `ProjectDirs`.
+#[derive(Debug, Clone)]
+pub struct ProjectDirs {
+    project_path: PathBuf,
+    cache_dir: PathBuf,
+    config_dir: PathBuf,
+    config_local_dir: PathBuf,
+    data_dir: PathBuf,
+    data_local_dir: PathBuf,
+    preference_dir: PathBuf,
+    runtime_dir: Option<PathBuf>,
+    state_dir: Option<PathBuf>,
+}
+
+impl ProjectDirs {
+    /// Create a new `ProjectDirs`.
+    /// All paths will be constructed based on the current user.
+    ///
+    /// # Arguments
+    ///
+    /// - `qualifier` - The reverse domain name notation of the application, e.g. `com` or `org`. Only used on macOS.
+    /// - `organization` - The name of the organization that develops the application. Not used on Linux.
+    /// - `application` - The name of the application itself.
+    ///
+    /// Returns an `AreiaResult` containing the `ProjectDirs`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::ProjectDirs;
+    ///
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App");
+    /// assert!(project.is_ok());
+    /// let project = project.unwrap();
+    /// if cfg!(target_os = "linux") {
+    ///     assert!(project.config_dir().ends_with("bar-app"));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if the application name is empty, the home directory could not be
+    /// found or an OS error occurred
+    pub fn new(qualifier: &str, organization: &str, application: &str) -> AreiaResult<ProjectDirs> {
+        let base_dirs = BaseDirs::new()?;
+        ProjectDirs::from_base_dirs(&base_dirs, qualifier, organization, application)
+    }
+
+    /// Create a new `ProjectDirs` in portable mode, inside the base directories of
+    /// `BaseDirs::portable()`.
+    ///
+    /// See `ProjectDirs::new()` for the other arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::ProjectDirs;
+    ///
+    /// if cfg!(unix) {
+    ///     let project = ProjectDirs::portable("/media/usb", "com", "Foo Corp", "Bar App").unwrap();
+    ///     assert!(project.config_dir().starts_with("/media/usb/config"));
+    ///     assert!(project.runtime_dir().is_none());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if `root` is relative or the application name is empty
+    pub fn portable<P: Into<PathBuf>>(
+        root: P,
+        qualifier: &str,
+        organization: &str,
+        application: &str,
+    ) -> AreiaResult<ProjectDirs> {
+        let base_dirs = BaseDirs::portable(root)?;
+        ProjectDirs::from_base_dirs(&base_dirs, qualifier, organization, application)
+    }
+
+    /// Create a new `ProjectDirs` from already resolved `BaseDirs`.
+    ///
+    /// See `ProjectDirs::new()` for the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, ProjectDirs};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let project = ProjectDirs::from_base_dirs(&base, "com", "Foo Corp", "Bar App").unwrap();
+    /// assert!(project.cache_dir().starts_with(base.cache_dir()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeSomething` if the application name is empty and
+    /// `AreiaError::InvalidProjectName` if a name is no single file name, e.g. contains a path
+    /// separator or is `..`
+    pub fn from_base_dirs(
+        base_dirs: &BaseDirs,
+        qualifier: &str,
+        organization: &str,
+        application: &str,
+    ) -> AreiaResult<ProjectDirs> {
+        // Surrounding whitespace is dropped on every OS, so the same names give the same paths
+        let [qualifier, organization, application] =
+            [qualifier, organization, application].map(str::trim);
+        for name in [qualifier, organization, application] {
+            if name.contains(['/', '\\']) || name == "." || name == ".." {
+                return Err(AreiaError::InvalidProjectName(name.to_owned()));
+            }
+        }
+        let project_path = project_path(qualifier, organization, application);
+        if application.is_empty() || project_path.as_os_str().is_empty() {
+            return Err(AreiaError::PathMustBeSomething(project_path));
+        }
+        if project_path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(AreiaError::InvalidProjectName(
+                project_path.to_string_lossy().into_owned(),
+            ));
+        }
+        let project_dir = |kind: DirKind, base: &Path| {
+            let mut dir = base.join(&project_path);
+            if let Some(subdir) = project_subdir(kind) {
+                dir.push(subdir);
+            }
+            dir
+        };
+        Ok(ProjectDirs {
+            cache_dir: project_dir(DirKind::Cache, base_dirs.cache_dir()),
+            config_dir: project_dir(DirKind::Config, base_dirs.config_dir()),
+            config_local_dir: project_dir(DirKind::ConfigLocal, base_dirs.config_local_dir()),
+            data_dir: project_dir(DirKind::Data, base_dirs.data_dir()),
+            data_local_dir: project_dir(DirKind::DataLocal, base_dirs.data_local_dir()),
+            preference_dir: project_dir(DirKind::Preference, base_dirs.preference_dir()),
+            runtime_dir: base_dirs
+                .runtime_dir()
+                .map(|dir| project_dir(DirKind::Runtime, dir)),
+            state_dir: base_dirs
+                .state_dir()
+                .map(|dir| project_dir(DirKind::State, dir)),
+            project_path,
+        })
+    }
+
+    /// Get the project path
+    ///
+    /// This is the relative path appended to every base directory.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `{application}` in lower-kebab-case | `{organization}\{application}` | `{qualifier}.{organization}.{application}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let project_path = project.project_path();
+    /// assert!(project_path.is_relative());
+    /// ```
+    #[must_use]
+    pub fn project_path(&self) -> &PathBuf {
+        &self.project_path
+    }
+
+    /// Get the project cache directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_CACHE_HOME/{project_path}` OR `$HOME/.cache/{project_path}` | `{FolderID::LocalAppData}\{project_path}\cache` | `$HOME/Library/Caches/{project_path}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let cache = project.cache_dir();
+    /// assert!(cache.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn cache_dir(&self) -> &PathBuf {
+        &self.cache_dir
+    }
+
+    /// Get the project config directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_CONFIG_HOME/{project_path}` OR `$HOME/.config/{project_path}` | `{FolderID::RoamingAppData}\{project_path}\config` | `$HOME/Library/Application Support/{project_path}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let config = project.config_dir();
+    /// assert!(config.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn config_dir(&self) -> &PathBuf {
+        &self.config_dir
+    }
+
+    /// Get the project config local directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_CONFIG_HOME/{project_path}` OR `$HOME/.config/{project_path}` | `{FolderID::LocalAppData}\{project_path}\config` | `$HOME/Library/Application Support/{project_path}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let config = project.config_local_dir();
+    /// assert!(config.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn config_local_dir(&self) -> &PathBuf {
+        &self.config_local_dir
+    }
+
+    /// Get the project data directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_DATA_HOME/{project_path}` OR `$HOME/.local/share/{project_path}` | `{FolderID::RoamingAppData}\{project_path}\data` | `$HOME/Library/Application Support/{project_path}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let data = project.data_dir();
+    /// assert!(data.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn data_dir(&self) -> &PathBuf {
+        &self.data_dir
+    }
+
+    /// Get the project data local directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_DATA_HOME/{project_path}` OR `$HOME/.local/share/{project_path}` | `{FolderID::LocalAppData}\{project_path}\data` | `$HOME/Library/Application Support/{project_path}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let data = project.data_local_dir();
+    /// assert!(data.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn data_local_dir(&self) -> &PathBuf {
+        &self.data_local_dir
+    }
+
+    /// Get the project preference directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `$XDG_CONFIG_HOME/{project_path}` OR `$HOME/.config/{project_path}` | `{FolderID::RoamingAppData}\{project_path}\config` | `$HOME/Library/Preferences/{project_path}` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let preference = project.preference_dir();
+    /// assert!(preference.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn preference_dir(&self) -> &PathBuf {
+        &self.preference_dir
+    }
+
+    /// Get the project runtime directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some($XDG_RUNTIME_DIR/{project_path})` OR `None` | `None` | `None` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let runtime = project.runtime_dir();
+    /// if runtime.is_some() {
+    ///     assert!(runtime.unwrap().is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn runtime_dir(&self) -> Option<&PathBuf> {
+        self.runtime_dir.as_ref()
+    }
+
+    /// Get the project state directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some($XDG_STATE_HOME/{project_path})` OR `Some($HOME/.local/state/{project_path})` | `None` | `None` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::ProjectDirs;
+    /// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    /// let state = project.state_dir();
+    /// if state.is_some() {
+    ///     assert!(state.unwrap().is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn state_dir(&self) -> Option<&PathBuf> {
+        self.state_dir.as_ref()
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+/// A requirement of the XDG spec the runtime directory does not fulfill
+///
+/// See `BaseDirs::validate_runtime_dir()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeDirViolation {
+    /// The path does not exist or is not a directory
+    NotADirectory,
+    /// The directory is owned by another user, contains the uid of the owner
+    WrongOwner(u32),
+    /// Other users have access to the directory, contains the permission bits
+    WrongMode(u32),
+    /// The directory is on a network filesystem, contains the magic number of the filesystem
+    NotLocal(u32),
+}
+
+impl Display for RuntimeDirViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeDirViolation::NotADirectory => write!(f, "not a directory"),
+            RuntimeDirViolation::WrongOwner(uid) => write!(f, "owned by uid {uid}"),
+            RuntimeDirViolation::WrongMode(mode) => write!(f, "mode {mode:o} instead of 700"),
+            RuntimeDirViolation::NotLocal(fs_type) => {
+                write!(f, "on a network filesystem ({fs_type:#x})")
+            }
+        }
+    }
+}
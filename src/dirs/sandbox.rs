@@ -0,0 +1,220 @@
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{BaseDirs, DirSource, EnvSource, UserInfo, error::AreiaResult};
+
+/// The file Flatpak places at the root of every sandbox
+pub(crate) const FLATPAK_INFO: &str = "/.flatpak-info";
+
+/// Variables that mark a sandbox, they are hidden from the host environment
+const SANDBOX_VARS: [&str; 4] = ["FLATPAK_ID", "SNAP", "SNAP_NAME", "SNAP_INSTANCE_NAME"];
+
+/// The XDG variables a sandbox points into itself
+///
+/// Flatpak keeps the host values in `HOST_{var}`, Snap drops them, so the defaults inside
+/// `$SNAP_REAL_HOME` are used.
+const REDIRECTED_VARS: [&str; 4] = [
+    "XDG_CACHE_HOME",
+    "XDG_CONFIG_HOME",
+    "XDG_DATA_HOME",
+    "XDG_STATE_HOME",
+];
+
+/// The kind of sandbox the application runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxKind {
+    /// Detected by `/.flatpak-info` or `$FLATPAK_ID`
+    Flatpak,
+    /// Detected by `$SNAP`
+    Snap,
+}
+
+impl Display for SandboxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SandboxKind::Flatpak => write!(f, "Flatpak"),
+            SandboxKind::Snap => write!(f, "Snap"),
+        }
+    }
+}
+
+/// The sandbox the application runs in
+///
+/// Inside a sandbox the base directories point into the sandbox, e.g.
+/// `~/.var/app/<app-id>/config` in Flatpak or `$SNAP_USER_DATA/.config` in Snap.
+/// The directories of the host are available with `Sandbox::host_dirs()`.
+///
+/// Get it with `BaseDirs::sandbox()`.
+///
+/// | Linux | Windows | macOS |
+/// | --- | --- | --- |
+/// | Flatpak, Snap | None | None |
+///
+/// # Example
+///
+/// ```
+/// use areia::BaseDirs;
+///
+/// let base = BaseDirs::new().unwrap();
+/// if let Some(sandbox) = base.sandbox() {
+///     println!("Running in {} as {:?}", sandbox.kind(), sandbox.app_id());
+///     println!("Host config: {}", sandbox.host_dirs().config_dir().display());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    kind: SandboxKind,
+    app_id: Option<String>,
+    user_common_dir: Option<PathBuf>,
+    host_dirs: Box<BaseDirs>,
+}
+
+impl Sandbox {
+    /// Detects the sandbox from `env`, `flatpak_info` is only checked for the process environment
+    pub(crate) fn detect(env: &dyn EnvSource, flatpak_info: Option<&Path>) -> Option<Sandbox> {
+        if !cfg!(target_os = "linux") {
+            return None;
+        }
+        let info = flatpak_info.and_then(|path| std::fs::read_to_string(path).ok());
+        let (kind, app_id) = if env.var_os("FLATPAK_ID").is_some() || info.is_some() {
+            let app_id = non_empty_var(env, "FLATPAK_ID")
+                .or_else(|| info.as_deref().and_then(flatpak_app_id));
+            (SandboxKind::Flatpak, app_id)
+        } else if env.var_os("SNAP").is_some() {
+            let app_id = non_empty_var(env, "SNAP_INSTANCE_NAME")
+                .or_else(|| non_empty_var(env, "SNAP_NAME"));
+            (SandboxKind::Snap, app_id)
+        } else {
+            return None;
+        };
+        let user_common_dir = match kind {
+            SandboxKind::Flatpak => None,
+            SandboxKind::Snap => env
+                .var_os("SNAP_USER_COMMON")
+                .map(PathBuf::from)
+                .filter(|dir| dir.is_absolute()),
+        };
+        let host_dirs = BaseDirs::from_env(&HostEnv { env, kind }).ok()?;
+        Some(Sandbox {
+            kind,
+            app_id,
+            user_common_dir,
+            host_dirs: Box::new(host_dirs),
+        })
+    }
+
+    /// Get the kind of the sandbox
+    #[must_use]
+    pub fn kind(&self) -> SandboxKind {
+        self.kind
+    }
+
+    /// Get the id of the application, e.g. `org.example.App` in Flatpak or the snap name
+    ///
+    /// Returns `None` if the sandbox does not provide it.
+    #[must_use]
+    pub fn app_id(&self) -> Option<&str> {
+        self.app_id.as_deref()
+    }
+
+    /// Get the directory of the user data shared by every revision of the snap
+    ///
+    /// The base directories are inside `$SNAP_USER_DATA`, which is copied for each revision, so
+    /// large data like caches is better kept here.
+    ///
+    /// | Flatpak | Snap |
+    /// | --- | --- |
+    /// | None | `$SNAP_USER_COMMON` |
+    #[must_use]
+    pub fn user_common_dir(&self) -> Option<&PathBuf> {
+        self.user_common_dir.as_ref()
+    }
+
+    /// Get the base directories of the host, outside of the sandbox
+    ///
+    /// | Flatpak | Snap |
+    /// | --- | --- |
+    /// | `$HOST_XDG_*_HOME` or the defaults in `$HOME` | The defaults in `$SNAP_REAL_HOME` or the home directory of the passwd database |
+    ///
+    /// Accessing them requires the matching sandbox permissions.
+    #[must_use]
+    pub fn host_dirs(&self) -> &BaseDirs {
+        &self.host_dirs
+    }
+}
+
+fn non_empty_var(env: &dyn EnvSource, key: &str) -> Option<String> {
+    env.var_os(key)
+        .and_then(|value| value.into_string().ok())
+        .filter(|value| !value.is_empty())
+}
+
+/// Reads the `name` of the `[Application]` group of `/.flatpak-info`
+fn flatpak_app_id(info: &str) -> Option<String> {
+    let mut in_application = false;
+    for line in info.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_application = line == "[Application]";
+        } else if in_application && let Some(name) = line.strip_prefix("name=") {
+            return Some(name.trim().to_owned()).filter(|name| !name.is_empty());
+        }
+    }
+    None
+}
+
+/// The environment of the host, derived from the environment inside the sandbox
+struct HostEnv<'a> {
+    env: &'a dyn EnvSource,
+    kind: SandboxKind,
+}
+
+impl EnvSource for HostEnv<'_> {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        if SANDBOX_VARS.contains(&key) {
+            return None;
+        }
+        match self.kind {
+            SandboxKind::Flatpak if REDIRECTED_VARS.contains(&key) => {
+                self.env.var_os(&format!("HOST_{key}"))
+            }
+            SandboxKind::Snap if REDIRECTED_VARS.contains(&key) => None,
+            // `/app` and the runtime are only search paths inside the sandbox
+            SandboxKind::Flatpak if key == "XDG_DATA_DIRS" || key == "XDG_CONFIG_DIRS" => None,
+            // Snap uses `$XDG_RUNTIME_DIR/snap.<name>` as runtime directory
+            SandboxKind::Snap if key == "XDG_RUNTIME_DIR" => {
+                let runtime = PathBuf::from(self.env.var_os(key)?);
+                match (runtime.parent(), runtime.file_name()) {
+                    (Some(parent), Some(name)) if name.to_string_lossy().starts_with("snap.") => {
+                        Some(parent.as_os_str().to_owned())
+                    }
+                    _ => Some(runtime.into_os_string()),
+                }
+            }
+            _ => self.env.var_os(key),
+        }
+    }
+
+    fn home_dir(&self) -> AreiaResult<PathBuf> {
+        match self.kind {
+            SandboxKind::Flatpak => self.env.home_dir(),
+            // Snap points `$HOME` to `$SNAP_USER_DATA`
+            SandboxKind::Snap => match self.env.var_os("SNAP_REAL_HOME") {
+                Some(home) => Ok(home.into()),
+                None => Ok(UserInfo::current()?.home().clone()),
+            },
+        }
+    }
+
+    fn home_dir_source(&self) -> DirSource {
+        match self.kind {
+            SandboxKind::Flatpak => self.env.home_dir_source(),
+            SandboxKind::Snap if self.env.var_os("SNAP_REAL_HOME").is_some() => {
+                DirSource::EnvVar("SNAP_REAL_HOME".to_owned())
+            }
+            SandboxKind::Snap => DirSource::Passwd,
+        }
+    }
+}
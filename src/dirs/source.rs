@@ -0,0 +1,125 @@
+use std::{
+    ffi::{OsStr, OsString},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::DirKind;
+
+/// The source a directory was resolved from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirSource {
+    /// Read from the contained environment variable, e.g. `XDG_CACHE_HOME`
+    EnvVar(String),
+    /// Read from the passwd database, because `$HOME` is not set
+    Passwd,
+    /// Supplied by the caller, e.g. the home directory of a `MapEnv`
+    Supplied,
+    /// Read from the `user-dirs.dirs` file of the user at the contained path
+    UserDirsFile(PathBuf),
+    /// Read from the system wide `user-dirs.defaults` file at the contained path
+    SystemDefaults(PathBuf),
+    /// Relocated inside the contained root of portable mode, see `BaseDirs::portable()`
+    Portable(PathBuf),
+    /// The default of the spec or the conventional default of the OS
+    Default,
+}
+
+impl Display for DirSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DirSource::EnvVar(var) => write!(f, "environment variable `{var}`"),
+            DirSource::Passwd => write!(f, "passwd database"),
+            DirSource::Supplied => write!(f, "supplied by the caller"),
+            DirSource::UserDirsFile(path) => write!(f, "user dirs file {}", path.display()),
+            DirSource::SystemDefaults(path) => {
+                write!(f, "system defaults file {}", path.display())
+            }
+            DirSource::Portable(root) => write!(f, "portable root {}", root.display()),
+            DirSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// How a single directory was resolved, see `BaseDirs::explain()`
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, DirKind, DirSource, MapEnv};
+/// use std::path::Path;
+///
+/// let env = MapEnv::new("/home/alice").with_var("XDG_CACHE_HOME", "relative/cache");
+/// let base = BaseDirs::from_env(&env).unwrap();
+/// let cache = base.explain().iter().find(|record| record.kind() == DirKind::Cache).unwrap();
+/// if cfg!(target_os = "linux") {
+///     assert_eq!(cache.path(), Some(Path::new("/home/alice/.cache")));
+///     assert_eq!(cache.source(), &DirSource::Default);
+///     assert_eq!(cache.rejected(), Some(("XDG_CACHE_HOME", "relative/cache".as_ref())));
+/// }
+/// println!("{cache}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    kind: DirKind,
+    path: Option<PathBuf>,
+    source: DirSource,
+    rejected: Option<(String, OsString)>,
+}
+
+impl Explanation {
+    pub(crate) fn new(
+        kind: DirKind,
+        path: Option<PathBuf>,
+        source: DirSource,
+        rejected: Option<(String, OsString)>,
+    ) -> Explanation {
+        Explanation {
+            kind,
+            path,
+            source,
+            rejected,
+        }
+    }
+
+    /// The kind of the directory
+    #[must_use]
+    pub fn kind(&self) -> DirKind {
+        self.kind
+    }
+
+    /// The resolved path, `None` if the directory is not available
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Where the path came from
+    #[must_use]
+    pub fn source(&self) -> &DirSource {
+        &self.source
+    }
+
+    /// The environment variable and its value, if it was set but ignored
+    ///
+    /// The XDG spec mandates that relative paths are ignored.
+    #[must_use]
+    pub fn rejected(&self) -> Option<(&str, &OsStr)> {
+        self.rejected
+            .as_ref()
+            .map(|(var, value)| (var.as_str(), value.as_os_str()))
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {} ({})", self.kind, path.display(), self.source)?,
+            None => write!(f, "{}: not available", self.kind)?,
+        }
+        if let Some((var, value)) = &self.rejected {
+            write!(f, ", ignored relative `{var}={}`", value.display())?;
+        }
+        Ok(())
+    }
+}
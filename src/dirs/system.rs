@@ -0,0 +1,204 @@
+use std::path::PathBuf;
+
+use crate::{
+    error::AreiaResult,
+    utils::factory::{
+        system_bin_dir, system_config_dir, system_data_dir, system_local_bin_dir, system_root_dir,
+        system_share_dir,
+    },
+};
+
+/// System directories
+///
+/// This struct contains all the machine-wide directories:
+///
+/// - `root_dir`
+/// - `bin_dir` (If available)
+/// - `local_bin_dir` (If available)
+/// - `data_dir` (If available)
+/// - `share_dir` (If available)
+/// - `config_dir` (If available)
+///
+/// These directories are shared by all users, writing to them usually requires elevated
+/// permissions.
+///
+/// Create with `SystemDirs::new()`.
+/// Get paths by using the provided functions on `SystemDirs`.
+#[allow(clippy::struct_field_names)]
+#[derive(Debug, Clone)]
+pub struct SystemDirs {
+    root_dir: PathBuf,
+    bin_dir: Option<PathBuf>,
+    local_bin_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    share_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+}
+
+impl SystemDirs {
+    /// Create a new `SystemDirs`.
+    ///
+    /// Returns an `AreiaResult` containing the `SystemDirs`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::SystemDirs;
+    ///
+    /// let system = SystemDirs::new();
+    /// assert!(system.is_ok());
+    /// let system = system.unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if the system drive could not be found (Windows only)
+    pub fn new() -> AreiaResult<SystemDirs> {
+        Ok(SystemDirs {
+            root_dir: system_root_dir()?,
+            bin_dir: system_bin_dir(),
+            local_bin_dir: system_local_bin_dir(),
+            data_dir: system_data_dir(),
+            share_dir: system_share_dir(),
+            config_dir: system_config_dir(),
+        })
+    }
+
+    /// Get the root directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `/` | System drive, e.g. `C:\` | `/` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::SystemDirs;
+    /// let system = SystemDirs::new().unwrap();
+    /// let root = system.root_dir();
+    /// assert!(root.is_absolute());
+    /// ```
+    #[must_use]
+    pub fn root_dir(&self) -> &PathBuf {
+        &self.root_dir
+    }
+
+    /// Get the system binary directory
+    ///
+    /// This directory is managed by the package manager of the OS.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some(/usr/bin)` | `None` | `Some(/usr/bin)` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::SystemDirs;
+    /// let system = SystemDirs::new().unwrap();
+    /// let bin = system.bin_dir();
+    /// if cfg!(unix) {
+    ///     assert!(bin.unwrap().is_absolute());
+    /// } else {
+    ///     assert!(bin.is_none());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn bin_dir(&self) -> Option<&PathBuf> {
+        self.bin_dir.as_ref()
+    }
+
+    /// Get the local system binary directory
+    ///
+    /// This directory is meant for software installed by the administrator.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some(/usr/local/bin)` | `Some({FolderID::ProgramFilesX86})` | `Some(/usr/local/bin)` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::SystemDirs;
+    /// let system = SystemDirs::new().unwrap();
+    /// let local_bin = system.local_bin_dir();
+    /// if local_bin.is_some() {
+    ///     assert!(local_bin.unwrap().is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn local_bin_dir(&self) -> Option<&PathBuf> {
+        self.local_bin_dir.as_ref()
+    }
+
+    /// Get the system data directory
+    ///
+    /// This directory holds variable, machine-wide application data.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some(/var/lib)` | `Some({FolderID::ProgramData})` | `Some(/Library/Application Support)` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::SystemDirs;
+    /// let system = SystemDirs::new().unwrap();
+    /// let data = system.data_dir();
+    /// if data.is_some() {
+    ///     assert!(data.unwrap().is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn data_dir(&self) -> Option<&PathBuf> {
+        self.data_dir.as_ref()
+    }
+
+    /// Get the system share directory
+    ///
+    /// This directory holds read-only, architecture independent data shipped with applications.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some(/usr/share)` | `None` | `Some(/usr/share)` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::SystemDirs;
+    /// let system = SystemDirs::new().unwrap();
+    /// let share = system.share_dir();
+    /// if cfg!(unix) {
+    ///     assert!(share.unwrap().is_absolute());
+    /// } else {
+    ///     assert!(share.is_none());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn share_dir(&self) -> Option<&PathBuf> {
+        self.share_dir.as_ref()
+    }
+
+    /// Get the system config directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some(/etc)` | `None` | `Some(/Library/Preferences)` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::SystemDirs;
+    /// let system = SystemDirs::new().unwrap();
+    /// let config = system.config_dir();
+    /// if cfg!(unix) {
+    ///     assert!(config.unwrap().is_absolute());
+    /// } else {
+    ///     assert!(config.is_none());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn config_dir(&self) -> Option<&PathBuf> {
+        self.config_dir.as_ref()
+    }
+}
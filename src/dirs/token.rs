@@ -0,0 +1,268 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::{
+    BaseDirs, DirKind, UserDirs,
+    error::{AreiaError, AreiaResult},
+};
+
+/// Encodes paths relative to the base and user directories as portable tokens
+///
+/// A token is the name of a `DirKind` in braces, followed by the path inside that directory
+/// with `/` as separator, e.g. `{data}/app/db.sqlite`.
+/// Tokens round-trip between machines, accounts and operating systems: the same token resolves
+/// to the matching directory of whoever opens it.
+/// Where directories coincide, e.g. `data` and `config` on Windows, `PathTokens::encode()` can't
+/// know which one is meant and picks one by a fixed order, see `PathTokens::classify()`.
+/// Encode config files, caches and anything else inside such a directory with
+/// `PathTokens::encode_as()`, otherwise e.g. a config file on Windows becomes a `{data}` token and
+/// resolves inside the data directory on Linux.
+///
+/// # Example
+///
+/// ```
+/// use areia::PathTokens;
+///
+/// let tokens = PathTokens::new().unwrap();
+/// let db = tokens.resolve("{data}/app/db.sqlite").unwrap();
+/// if cfg!(target_os = "linux") {
+///     assert_eq!(tokens.encode(&db).as_deref(), Some("{data}/app/db.sqlite"));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathTokens {
+    dirs: Vec<(DirKind, PathBuf)>,
+}
+
+impl PathTokens {
+    /// Create a new `PathTokens` from the directories of the current user
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::PathTokens;
+    ///
+    /// let tokens = PathTokens::new();
+    /// assert!(tokens.is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if the home directory could not be found or an OS error occurred
+    pub fn new() -> AreiaResult<PathTokens> {
+        Ok(PathTokens::from_dirs(&BaseDirs::new()?, &UserDirs::new()?))
+    }
+
+    /// Create a new `PathTokens` from already resolved directories
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, MapEnv, PathTokens, UserDirs};
+    ///
+    /// let env = MapEnv::new("/home/alice");
+    /// let base = BaseDirs::from_env(&env).unwrap();
+    /// let user = UserDirs::from_env(&env).unwrap();
+    /// let tokens = PathTokens::from_dirs(&base, &user);
+    /// assert!(tokens.resolve("{home}/notes").unwrap().starts_with("/home/alice"));
+    /// ```
+    #[must_use]
+    pub fn from_dirs(base_dirs: &BaseDirs, user_dirs: &UserDirs) -> PathTokens {
+        let mut dirs: Vec<(DirKind, PathBuf)> = base_dirs
+            .iter()
+            .map(|(kind, path)| (kind, path.to_path_buf()))
+            .collect();
+        dirs.extend(
+            user_dirs
+                .iter()
+                .filter(|(kind, _)| *kind != DirKind::Home)
+                .map(|(kind, path)| (kind, path.to_path_buf())),
+        );
+        PathTokens { dirs }
+    }
+
+    /// Find the most specific directory `path` is inside of
+    ///
+    /// Returns the kind of the directory and the rest of `path` relative to it.
+    /// If several directories are the same path, a fixed order decides, which can't know the
+    /// meant kind: `data` beats `config` (the same on Windows and macOS), `data-local` beats
+    /// `cache` and `config-local` (the same on Windows) and `config` beats `config-local` and
+    /// `preference` (the same on Linux).
+    /// Otherwise, the first kind in the order of `DirKind::ALL` wins.
+    /// Use `PathTokens::encode_as()` for paths inside such directories.
+    ///
+    /// Returns `None` if `path` is inside none of the directories.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, DirKind, PathTokens};
+    /// use std::path::Path;
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let tokens = PathTokens::new().unwrap();
+    /// let (kind, rest) = tokens.classify(base.data_dir().join("app/index")).unwrap();
+    /// assert_eq!(kind, DirKind::Data);
+    /// assert_eq!(rest, Path::new("app/index"));
+    /// ```
+    #[must_use]
+    pub fn classify<P: AsRef<Path>>(&self, path: P) -> Option<(DirKind, PathBuf)> {
+        let path = path.as_ref();
+        let mut best: Option<(DirKind, &Path, usize)> = None;
+        for (kind, dir) in &self.dirs {
+            let Ok(rest) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let depth = dir.components().count();
+            if best.is_none_or(|(best_kind, _, best_depth)| {
+                depth > best_depth || (depth == best_depth && tie_rank(*kind) < tie_rank(best_kind))
+            }) {
+                best = Some((*kind, rest, depth));
+            }
+        }
+        best.map(|(kind, rest, _)| (kind, rest.to_path_buf()))
+    }
+
+    /// Encode `path` as token, relative to the most specific directory it is inside of
+    ///
+    /// See `PathTokens::classify()`.
+    ///
+    /// Returns `None` if `path` is inside none of the directories or the rest of the path is
+    /// not valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, PathTokens};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let tokens = PathTokens::new().unwrap();
+    /// let token = tokens.encode(base.data_dir().join("app/db.sqlite"));
+    /// assert_eq!(token.as_deref(), Some("{data}/app/db.sqlite"));
+    /// ```
+    #[must_use]
+    pub fn encode<P: AsRef<Path>>(&self, path: P) -> Option<String> {
+        let (kind, rest) = self.classify(path)?;
+        token(kind, &rest)
+    }
+
+    /// Encode `path` as token relative to the directory of `kind`
+    ///
+    /// Use this where directories coincide and the caller knows which one it means, e.g. a
+    /// cache on Windows, where `cache` and `data-local` are the same directory, or a config file
+    /// on Windows and macOS, where `config` and `data` are.
+    ///
+    /// Returns `None` if `path` is not inside the directory of `kind`, the directory does not
+    /// exist on this OS or the rest of the path is not valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, DirKind, PathTokens};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let tokens = PathTokens::new().unwrap();
+    /// let token = tokens.encode_as(DirKind::Cache, base.cache_dir().join("app/index"));
+    /// assert_eq!(token.as_deref(), Some("{cache}/app/index"));
+    /// ```
+    #[must_use]
+    pub fn encode_as<P: AsRef<Path>>(&self, kind: DirKind, path: P) -> Option<String> {
+        let (_, dir) = self.dirs.iter().find(|(dir_kind, _)| *dir_kind == kind)?;
+        let rest = path.as_ref().strip_prefix(dir).ok()?;
+        token(kind, rest)
+    }
+
+    /// Resolve a token created by `PathTokens::encode()` to a path
+    ///
+    /// Strings that do not start with a token are returned as path unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{BaseDirs, PathTokens};
+    ///
+    /// let base = BaseDirs::new().unwrap();
+    /// let tokens = PathTokens::new().unwrap();
+    /// let path = tokens.resolve("{data}/app/db.sqlite").unwrap();
+    /// assert_eq!(path, base.data_dir().join("app").join("db.sqlite"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UnknownDirKind` if the token is no `DirKind`,
+    /// `AreiaError::DirectoryNotAvailable` if the directory does not exist on this OS, e.g.
+    /// `{runtime}` on macOS, and `AreiaError::InvalidToken` if the `}` is not followed by `/` or
+    /// the rest of the token is not a plain relative path, e.g. leaves the directory with `..`.
+    pub fn resolve(&self, token: &str) -> AreiaResult<PathBuf> {
+        let Some((name, rest)) = token
+            .strip_prefix('{')
+            .and_then(|token| token.split_once('}'))
+        else {
+            return Ok(PathBuf::from(token));
+        };
+        let kind: DirKind = name.parse()?;
+        let mut path = self
+            .dirs
+            .iter()
+            .find(|(dir_kind, _)| *dir_kind == kind)
+            .map(|(_, dir)| dir.clone())
+            .ok_or(AreiaError::DirectoryNotAvailable(kind))?;
+        let Some(rest) = rest.strip_prefix('/').or(rest.is_empty().then_some("")) else {
+            return Err(AreiaError::InvalidToken(token.to_owned()));
+        };
+        let rest = Path::new(rest);
+        if rest
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(AreiaError::InvalidToken(token.to_owned()));
+        }
+        path.extend(rest.components());
+        Ok(path)
+    }
+}
+
+/// Create `PathTokens` from the directory of every kind, e.g. to encode paths of another machine
+///
+/// # Example
+///
+/// ```
+/// use areia::{DirKind, PathTokens};
+/// use std::path::PathBuf;
+///
+/// let tokens: PathTokens = [(DirKind::Data, PathBuf::from("/srv/data"))].into_iter().collect();
+/// assert_eq!(tokens.encode("/srv/data/db").as_deref(), Some("{data}/db"));
+/// ```
+impl FromIterator<(DirKind, PathBuf)> for PathTokens {
+    fn from_iter<I: IntoIterator<Item = (DirKind, PathBuf)>>(iter: I) -> Self {
+        PathTokens {
+            dirs: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// The token of `rest` inside the directory of `kind`
+fn token(kind: DirKind, rest: &Path) -> Option<String> {
+    let mut token = format!("{{{kind}}}");
+    for component in rest.components() {
+        token.push('/');
+        token.push_str(component.as_os_str().to_str()?);
+    }
+    Some(token)
+}
+
+/// The rank of `kind` if several directories are the same path, the lowest wins
+///
+/// Only makes `PathTokens::classify()` deterministic, the meant kind is up to the caller.
+fn tie_rank(kind: DirKind) -> usize {
+    const PREFERRED: [DirKind; 4] = [
+        DirKind::Data,
+        DirKind::DataLocal,
+        DirKind::Config,
+        DirKind::ConfigLocal,
+    ];
+    PREFERRED
+        .iter()
+        .chain(DirKind::ALL.iter())
+        .position(|preferred| *preferred == kind)
+        .unwrap_or(usize::MAX)
+}
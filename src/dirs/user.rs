@@ -1,10 +1,30 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
 use crate::{
-    error::AreiaResult,
-    utils::{factory::get_usr_dirs, get_home},
+    DirKind, DirSource, EnvSource, ProcessEnv, User,
+    env::PasswdEnv,
+    error::{AreiaError, AreiaResult},
+    utils::{
+        create_private_dir_all,
+        factory::{UsrDirs, get_usr_dirs, user_dirs_file, write_usr_dirs},
+    },
 };
 
+/// The user directories that are part of `user-dirs.dirs`, fonts are not configurable
+const STANDARD_DIRS: [DirKind; 8] = [
+    DirKind::Desktop,
+    DirKind::Document,
+    DirKind::Download,
+    DirKind::Audio,
+    DirKind::Picture,
+    DirKind::Public,
+    DirKind::Template,
+    DirKind::Video,
+];
+
 /// User directories
 ///
 /// This struct contains all the user directories:
@@ -19,6 +39,10 @@ use crate::{
 /// - `public_dir`
 /// - `template_dir`
 /// - `video_dir`
+/// - `screenshot_dir`
+/// - `project_dir`
+///
+/// All other `XDG_xxx_DIR` entries of `user-dirs.dirs` are available with `UserDirs::custom()`.
 ///
 /// Create with `UserDirs::new()`.
 /// Get paths by using the provided functions on `UserDirs`.
@@ -34,6 +58,11 @@ pub struct UserDirs {
     public_dir: Option<PathBuf>,
     template_dir: Option<PathBuf>,
     video_dir: Option<PathBuf>,
+    screenshot_dir: Option<PathBuf>,
+    project_dir: Option<PathBuf>,
+    custom: BTreeMap<String, PathBuf>,
+    sources: HashMap<DirKind, DirSource>,
+    dirs_file: Option<PathBuf>,
 }
 
 impl UserDirs {
@@ -44,7 +73,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// use areia::UserDirs;
     ///
     /// let user = UserDirs::new();
@@ -55,8 +84,98 @@ impl UserDirs {
     /// # Errors
     ///
     /// Returns an `AreiaError` if the home directory could not be found or an OS error occurred
+    ///
+    /// A missing `user-dirs.dirs` file on Linux is not an error, see `UserDirs::source()`.
     pub fn new() -> AreiaResult<UserDirs> {
-        user_dirs()
+        user_dirs(&ProcessEnv)
+    }
+
+    /// Create a new `UserDirs` from a caller supplied environment.
+    /// All paths will be constructed based on the environment variables and home directory of
+    /// `env`, the environment of the process is not read.
+    ///
+    /// On Windows and macOS the user directories are known folders of the current user, only the
+    /// `home_dir` is taken from `env`.
+    ///
+    /// Returns an `AreiaResult` containing the `UserDirs`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{MapEnv, UserDirs};
+    /// use std::path::PathBuf;
+    ///
+    /// let env = MapEnv::new("/home/alice");
+    /// let user = UserDirs::from_env(&env).unwrap();
+    /// assert_eq!(user.home_dir(), &PathBuf::from("/home/alice"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AreiaError` if `env` provides no home directory or an OS error occurred
+    pub fn from_env(env: &dyn EnvSource) -> AreiaResult<UserDirs> {
+        user_dirs(env)
+    }
+
+    /// Create a new `UserDirs` for another user, addressed by name or uid.
+    /// The home directory is read from the passwd database, on Linux the `user-dirs.dirs` file
+    /// inside the home directory of the user is read.
+    ///
+    /// The environment variables of the other user are unknown, so `user-dirs.dirs` is expected
+    /// in the default config directory `$HOME/.config`.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | Supported | Error | Supported |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::UserDirs;
+    ///
+    /// if cfg!(unix) {
+    ///     let root = UserDirs::for_user("root").unwrap();
+    ///     assert!(root.home_dir().is_absolute());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the user does not exist,
+    /// `AreiaError::OtherUsersNotSupported` on Windows or an `AreiaError` if an OS error occurred
+    pub fn for_user<U: Into<User>>(user: U) -> AreiaResult<UserDirs> {
+        user_dirs(&PasswdEnv::new(&user.into())?)
+    }
+
+    /// Create a new `UserDirs` for the user who invoked the process through `sudo` or `pkexec`.
+    ///
+    /// Without `sudo` or `pkexec` this is the same as `UserDirs::new()`, otherwise the same as
+    /// `UserDirs::for_user()` with the user detected by `User::invoking()`.
+    /// This keeps tools run with elevated privileges from writing into the home directory of
+    /// root.
+    /// Files created for the invoking user can be handed over with `User::chown()` or created
+    /// with `auto_creator_for()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{UserDirs, User};
+    ///
+    /// let dirs = UserDirs::for_invoking_user().unwrap();
+    /// if User::invoking().is_none() {
+    ///     assert_eq!(dirs.home_dir(), UserDirs::new().unwrap().home_dir());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the invoking user does not exist or any error of
+    /// `UserDirs::new()`
+    pub fn for_invoking_user() -> AreiaResult<UserDirs> {
+        match User::invoking() {
+            Some(user) => user_dirs(&PasswdEnv::new(&user)?),
+            None => user_dirs(&ProcessEnv),
+        }
     }
 
     /// Get the home directory
@@ -67,7 +186,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let home = user.home_dir();
@@ -85,7 +204,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let audio = user.audio_dir();
@@ -105,7 +224,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let desktop = user.desktop_dir();
@@ -125,7 +244,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let document = user.document_dir();
@@ -145,7 +264,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let download = user.download_dir();
@@ -165,7 +284,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let font = user.font_dir();
@@ -185,7 +304,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let picture = user.picture_dir();
@@ -205,7 +324,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let public = user.public_dir();
@@ -225,7 +344,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let template = user.template_dir();
@@ -245,7 +364,7 @@ impl UserDirs {
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```
     /// # use areia::UserDirs;
     /// let user = UserDirs::new().unwrap();
     /// let video = user.video_dir();
@@ -256,48 +375,351 @@ impl UserDirs {
     pub fn video_dir(&self) -> &Option<PathBuf> {
         &self.video_dir
     }
+
+    /// Get the screenshot directory
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some($XDG_SCREENSHOTS_DIR)` OR `Some($XDG_PICTURES_DIR/Screenshots)` OR `None` | `Some({FolderID::Screenshots})` OR `None` | `Some($HOME/Desktop)` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::UserDirs;
+    /// let user = UserDirs::new().unwrap();
+    /// let screenshot = user.screenshot_dir();
+    /// if screenshot.is_some() {
+    ///     assert!(screenshot.as_ref().unwrap().is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn screenshot_dir(&self) -> &Option<PathBuf> {
+        &self.screenshot_dir
+    }
+
+    /// Get the project directory, the directory the user keeps their (software) projects in
+    ///
+    /// Linux and Windows default to the folders common tools create, macOS has no such
+    /// convention, so it is `None` there.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some($XDG_PROJECTS_DIR)` OR `Some($HOME/Projects)` | `Some({FolderID::Profile}\source\repos)` | `None` |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::UserDirs;
+    /// let user = UserDirs::new().unwrap();
+    /// let project = user.project_dir();
+    /// if project.is_some() {
+    ///     assert!(project.as_ref().unwrap().is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn project_dir(&self) -> &Option<PathBuf> {
+        &self.project_dir
+    }
+
+    /// Get any `XDG_xxx_DIR` entry by its key, e.g. `"PROJECTS"` or `"XDG_PROJECTS_DIR"`
+    ///
+    /// The keys are the ones used in `user-dirs.dirs`, so the download directory is `"DOWNLOAD"`.
+    /// Also returns the directories that have their own getter, e.g. `"DESKTOP"`.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `Some($XDG_{key}_DIR)` OR `None` | Only the directories with a getter | Only the directories with a getter |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::UserDirs;
+    /// let user = UserDirs::new().unwrap();
+    /// assert_eq!(user.custom("XDG_DESKTOP_DIR"), user.desktop_dir().as_deref());
+    /// if let Some(games) = user.custom("GAMES") {
+    ///     assert!(games.is_absolute());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn custom(&self, key: &str) -> Option<&Path> {
+        let key = key.strip_prefix("XDG_").unwrap_or(key);
+        let key = key.strip_suffix("_DIR").unwrap_or(key);
+        self.xdg_dirs()
+            .find(|(name, _)| *name == key)
+            .map(|(_, path)| path)
+    }
+
+    /// Iterate over every `XDG_xxx_DIR` entry that is set, as pairs of key and path
+    ///
+    /// Contains the directories with a getter (except the home and font directory) and all
+    /// additional entries of `user-dirs.dirs`.
+    /// The keys are the ones used in `user-dirs.dirs`, see `UserDirs::custom()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use areia::UserDirs;
+    /// let user = UserDirs::new().unwrap();
+    /// for (key, path) in user.xdg_dirs() {
+    ///     assert_eq!(user.custom(key), Some(path));
+    /// }
+    /// ```
+    pub fn xdg_dirs(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.iter()
+            .filter_map(|(kind, path)| Some((kind.xdg_key()?, path)))
+            .chain(
+                self.custom
+                    .iter()
+                    .map(|(key, path)| (key.as_str(), path.as_path())),
+            )
+    }
+
+    /// Get a directory by its kind
+    ///
+    /// Returns `None` if the directory is not available on this OS or `kind` is a base directory
+    /// other than `DirKind::Home`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{DirKind, UserDirs};
+    ///
+    /// let user = UserDirs::new().unwrap();
+    /// assert_eq!(user.get(DirKind::Home), Some(user.home_dir().as_path()));
+    /// assert_eq!(user.get(DirKind::Desktop), user.desktop_dir().as_deref());
+    /// assert!(user.get(DirKind::Cache).is_none());
+    /// ```
+    #[must_use]
+    pub fn get(&self, kind: DirKind) -> Option<&Path> {
+        if kind == DirKind::Home {
+            return Some(&self.home_dir);
+        }
+        self.dir(kind)?.as_deref()
+    }
+
+    /// Iterate over every available directory, including the home directory
+    ///
+    /// The directories are yielded in the order of `DirKind::ALL`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{DirKind, UserDirs};
+    ///
+    /// let user = UserDirs::new().unwrap();
+    /// for (kind, path) in user.iter() {
+    ///     assert_eq!(user.get(kind), Some(path));
+    /// }
+    /// assert_eq!(user.iter().next().map(|(kind, _)| kind), Some(DirKind::Home));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (DirKind, &Path)> {
+        DirKind::ALL
+            .into_iter()
+            .filter_map(|kind| Some((kind, self.get(kind)?)))
+    }
+
+    /// Get the source a user directory was resolved from
+    ///
+    /// On Linux the directories are read from `user-dirs.dirs` inside `BaseDirs::config_dir()`.
+    /// If that file does not exist, the first `user-dirs.defaults` of `BaseDirs::config_dirs()`
+    /// (usually `/etc/xdg/user-dirs.defaults`) is used and the conventional names
+    /// (e.g. `$HOME/Desktop`) as a last resort.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | `UserDirsFile` OR `SystemDefaults` OR `Default` | `Default` | `Default` |
+    ///
+    /// The home directory is resolved like `BaseDirs::home_dir()`, see `BaseDirs::explain()`.
+    ///
+    /// Returns `None` if `kind` is neither a user directory nor `DirKind::Home`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{DirKind, DirSource, UserDirs};
+    ///
+    /// let user = UserDirs::new().unwrap();
+    /// let source = user.source(DirKind::Download);
+    /// assert!(source.is_some());
+    /// if let Some(DirSource::UserDirsFile(file)) = source {
+    ///     assert!(file.ends_with("user-dirs.dirs"));
+    /// }
+    /// assert!(user.source(DirKind::Home).is_some());
+    /// assert!(user.source(DirKind::Cache).is_none());
+    /// ```
+    #[must_use]
+    pub fn source(&self, kind: DirKind) -> Option<&DirSource> {
+        self.sources.get(&kind)
+    }
+
+    /// Set a user directory, the equivalent of `xdg-user-dirs-update --set`
+    ///
+    /// The entry of `kind` inside `user-dirs.dirs` is replaced, or added if it is missing.
+    /// Paths inside the home directory are written relative to `$HOME`, all other lines and
+    /// comments of the file are preserved.
+    /// The file is replaced atomically and created if it does not exist yet.
+    /// The directory itself is not created, see `UserDirs::bootstrap()`.
+    ///
+    /// Only the Linux user directories are configurable, fonts are not part of `user-dirs.dirs`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use areia::{DirKind, UserDirs};
+    ///
+    /// let mut user = UserDirs::new().unwrap();
+    /// let incoming = user.home_dir().join("Incoming");
+    /// user.set_dir(DirKind::Download, &incoming).unwrap();
+    /// assert_eq!(user.download_dir(), &Some(incoming));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeAbsolute` if `path` is relative.
+    /// Returns `AreiaError::WritingUserDirsNotSupported` on Windows and macOS, if `kind` is not
+    /// part of `user-dirs.dirs` or if `path` contains a line break.
+    /// Returns `AreiaError::IoError` if the file could not be written.
+    pub fn set_dir<P: Into<PathBuf>>(&mut self, kind: DirKind, path: P) -> AreiaResult<()> {
+        let path = path.into();
+        if !path.is_absolute() {
+            return Err(AreiaError::PathMustBeAbsolute(path));
+        }
+        let Some(key) = kind.xdg_key() else {
+            return Err(AreiaError::WritingUserDirsNotSupported(format!(
+                "{kind} is not part of `user-dirs.dirs`"
+            )));
+        };
+        let file = self.writable_user_dirs_file()?;
+        write_usr_dirs(&file, &self.home_dir, &[(key, Some(&path))])?;
+
+        self.sources.insert(kind, DirSource::UserDirsFile(file));
+        if let Some(dir) = self.dir_mut(kind) {
+            *dir = Some(path);
+        }
+        Ok(())
+    }
+
+    /// Create all missing user directories and write them to `user-dirs.dirs`, the equivalent of
+    /// `xdg-user-dirs-update`
+    ///
+    /// Disabled directories (`None`) are neither created nor enabled. Like `BaseDirs::ensure()`,
+    /// missing directories and parents are created with the permission `0700` on Unix.
+    /// Afterwards every Linux user directory has the source `DirSource::UserDirsFile`.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | Creates the directories and writes `user-dirs.dirs` | Creates the directories | Creates the directories |
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use areia::UserDirs;
+    ///
+    /// let mut user = UserDirs::new().unwrap();
+    /// user.bootstrap().unwrap();
+    /// assert!(user.desktop_dir().as_ref().unwrap().is_dir());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::IoError` if a directory could not be created or the file could not be
+    /// written.
+    pub fn bootstrap(&mut self) -> AreiaResult<()> {
+        for kind in STANDARD_DIRS {
+            if let Some(Some(dir)) = self.dir(kind) {
+                create_private_dir_all(dir)?;
+            }
+        }
+
+        let Some(file) = self.dirs_file.clone() else {
+            return Ok(());
+        };
+        let dirs = STANDARD_DIRS
+            .iter()
+            .filter_map(|kind| Some((kind.xdg_key()?, self.dir(*kind)?.as_deref())))
+            .collect::<Vec<(&str, Option<&Path>)>>();
+        write_usr_dirs(&file, &self.home_dir, &dirs)?;
+
+        for kind in STANDARD_DIRS {
+            self.sources
+                .insert(kind, DirSource::UserDirsFile(file.clone()));
+        }
+        Ok(())
+    }
+
+    /// The `user-dirs.dirs` file the directories are read from, `None` if the OS has none
+    pub(crate) fn dirs_file(&self) -> Option<&Path> {
+        self.dirs_file.as_deref()
+    }
+
+    fn writable_user_dirs_file(&self) -> AreiaResult<PathBuf> {
+        self.dirs_file.clone().ok_or_else(|| {
+            AreiaError::WritingUserDirsNotSupported("No `user-dirs.dirs` on this OS".to_string())
+        })
+    }
+
+    fn dir(&self, kind: DirKind) -> Option<&Option<PathBuf>> {
+        match kind {
+            DirKind::Audio => Some(&self.audio_dir),
+            DirKind::Desktop => Some(&self.desktop_dir),
+            DirKind::Document => Some(&self.document_dir),
+            DirKind::Download => Some(&self.download_dir),
+            DirKind::Font => Some(&self.font_dir),
+            DirKind::Picture => Some(&self.picture_dir),
+            DirKind::Public => Some(&self.public_dir),
+            DirKind::Template => Some(&self.template_dir),
+            DirKind::Video => Some(&self.video_dir),
+            DirKind::Screenshot => Some(&self.screenshot_dir),
+            DirKind::Project => Some(&self.project_dir),
+            _ => None,
+        }
+    }
+
+    fn dir_mut(&mut self, kind: DirKind) -> Option<&mut Option<PathBuf>> {
+        match kind {
+            DirKind::Audio => Some(&mut self.audio_dir),
+            DirKind::Desktop => Some(&mut self.desktop_dir),
+            DirKind::Document => Some(&mut self.document_dir),
+            DirKind::Download => Some(&mut self.download_dir),
+            DirKind::Font => Some(&mut self.font_dir),
+            DirKind::Picture => Some(&mut self.picture_dir),
+            DirKind::Public => Some(&mut self.public_dir),
+            DirKind::Template => Some(&mut self.template_dir),
+            DirKind::Video => Some(&mut self.video_dir),
+            DirKind::Screenshot => Some(&mut self.screenshot_dir),
+            DirKind::Project => Some(&mut self.project_dir),
+            _ => None,
+        }
+    }
 }
 
-fn user_dirs() -> AreiaResult<UserDirs> {
-    let home_dir = get_home()?;
-
-    let usr_dirs = get_usr_dirs(home_dir.clone())?;
-    let audio_dir = usr_dirs
-        .get("MUSIC")
-        .expect("Key is always created by factory")
-        .clone();
-    let desktop_dir = usr_dirs
-        .get("DESKTOP")
-        .expect("Key is always created by factory")
-        .clone();
-    let document_dir = usr_dirs
-        .get("DOCUMENTS")
-        .expect("Key is always created by factory")
-        .clone();
-    let download_dir = usr_dirs
-        .get("DOWNLOADS")
-        .expect("Key is always created by factory")
-        .clone();
-    let font_dir = usr_dirs
-        .get("FONTS")
-        .expect("Key is always created by factory")
-        .clone();
-    let picture_dir = usr_dirs
-        .get("PICTURES")
-        .expect("Key is always created by factory")
-        .clone();
-    let public_dir = usr_dirs
-        .get("PUBLICSHARE")
-        .expect("Key is always created by factory")
-        .clone();
-    let template_dir = usr_dirs
-        .get("TEMPLATES")
-        .expect("Key is always created by factory")
-        .clone();
-    let video_dir = usr_dirs
-        .get("VIDEOS")
-        .expect("Key is always created by factory")
-        .clone();
+fn user_dirs(env: &dyn EnvSource) -> AreiaResult<UserDirs> {
+    let home_dir = env.home_dir()?;
+    let dirs_file = user_dirs_file(env, home_dir.clone());
+
+    let UsrDirs {
+        dirs: mut usr_dirs,
+        custom,
+    } = get_usr_dirs(env, home_dir.clone())?;
+    let mut sources = HashMap::from([(DirKind::Home, env.home_dir_source())]);
+    let mut take = |kind: DirKind| {
+        let (dir, source) = usr_dirs
+            .remove(&kind)
+            .expect("Kind is always created by factory");
+        sources.insert(kind, source);
+        dir
+    };
+    let audio_dir = take(DirKind::Audio);
+    let desktop_dir = take(DirKind::Desktop);
+    let document_dir = take(DirKind::Document);
+    let download_dir = take(DirKind::Download);
+    let font_dir = take(DirKind::Font);
+    let picture_dir = take(DirKind::Picture);
+    let public_dir = take(DirKind::Public);
+    let template_dir = take(DirKind::Template);
+    let video_dir = take(DirKind::Video);
+    let screenshot_dir = take(DirKind::Screenshot);
+    let project_dir = take(DirKind::Project);
 
     Ok(UserDirs {
         home_dir,
@@ -310,5 +732,10 @@ fn user_dirs() -> AreiaResult<UserDirs> {
         public_dir,
         template_dir,
         video_dir,
+        screenshot_dir,
+        project_dir,
+        custom,
+        sources,
+        dirs_file,
     })
 }
@@ -0,0 +1,459 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    DirSource,
+    error::{AreiaError, AreiaResult},
+    utils::{
+        change_owner_to, get_current_user_info, get_home, get_home_source, get_user_home,
+        get_user_info, is_privileged,
+    },
+};
+
+/// Source of the environment used to resolve directories
+///
+/// All directories are resolved from the environment variables and the home directory
+/// provided by an `EnvSource`.
+/// `ProcessEnv` reads the environment of the current process and is used by default, e.g. by
+/// `BaseDirs::new()`.
+/// `MapEnv` resolves directories from a caller supplied environment instead.
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, EnvSource, MapEnv};
+///
+/// let env = MapEnv::new("/home/alice").with_var("XDG_CACHE_HOME", "/var/cache/alice");
+/// assert_eq!(env.home_dir().unwrap(), std::path::PathBuf::from("/home/alice"));
+/// let base = BaseDirs::from_env(&env).unwrap();
+/// assert_eq!(base.home_dir(), &std::path::PathBuf::from("/home/alice"));
+/// ```
+pub trait EnvSource {
+    /// Returns the value of the environment variable `key`, if it is set
+    fn var_os(&self, key: &str) -> Option<OsString>;
+    /// Returns the home directory of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::CantGetHomeDir` if the home directory could not be found
+    fn home_dir(&self) -> AreiaResult<PathBuf>;
+    /// Returns where the home directory of `EnvSource::home_dir()` comes from
+    ///
+    /// Defaults to `DirSource::Supplied`.
+    fn home_dir_source(&self) -> DirSource {
+        DirSource::Supplied
+    }
+}
+
+/// The environment of the current process
+///
+/// Environment variables are read with `std::env::var_os`, the home directory is resolved from
+/// `$HOME` with a fallback to the OS (the passwd database on Unix, `{FolderID::Profile}` on
+/// Windows).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnv;
+
+impl EnvSource for ProcessEnv {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+
+    fn home_dir(&self) -> AreiaResult<PathBuf> {
+        get_home()
+    }
+
+    fn home_dir_source(&self) -> DirSource {
+        get_home_source()
+    }
+}
+
+/// A user of the system, either addressed by name or by uid
+///
+/// Used to resolve the directories of another user, e.g. with `BaseDirs::for_user()`.
+///
+/// # Example
+///
+/// ```
+/// use areia::User;
+///
+/// assert_eq!(User::from("alice"), User::Name("alice".to_owned()));
+/// assert_eq!(User::from(1000), User::Uid(1000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum User {
+    Name(String),
+    Uid(u32),
+}
+
+impl User {
+    /// The user who invoked the current process through `sudo` or `pkexec`
+    ///
+    /// Returns `None` if the process was not started by either of them or does not run as root,
+    /// any user could set the variables otherwise.
+    /// See `User::invoking_from()` for how the user is detected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::User;
+    ///
+    /// if let Some(user) = User::invoking() {
+    ///     println!("Started by {user}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn invoking() -> Option<User> {
+        // Anyone can set `SUDO_UID`, it only means something if the process really runs as root
+        if !is_privileged() {
+            return None;
+        }
+        User::invoking_from(&ProcessEnv)
+    }
+
+    /// The user who invoked a process with the environment `env` through `sudo` or `pkexec`
+    ///
+    /// `SUDO_UID` is preferred over `SUDO_USER`, `PKEXEC_UID` is used last.
+    /// Values that are empty or no valid uid are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{MapEnv, User};
+    ///
+    /// let env = MapEnv::new("/root").with_var("SUDO_USER", "alice");
+    /// assert_eq!(User::invoking_from(&env), Some(User::Name("alice".to_owned())));
+    /// assert_eq!(User::invoking_from(&MapEnv::new("/root")), None);
+    /// ```
+    #[must_use]
+    pub fn invoking_from(env: &dyn EnvSource) -> Option<User> {
+        let uid = |key| {
+            env.var_os(key)
+                .and_then(|uid| uid.to_str()?.parse::<u32>().ok())
+                .map(User::Uid)
+        };
+        uid("SUDO_UID")
+            .or_else(|| {
+                env.var_os("SUDO_USER")
+                    .and_then(|name| name.into_string().ok())
+                    .filter(|name| !name.is_empty())
+                    .map(User::Name)
+            })
+            .or_else(|| uid("PKEXEC_UID"))
+    }
+
+    /// Hands `path` over to this user and their primary group
+    ///
+    /// Use this after creating files as root on behalf of the invoking user, e.g. with
+    /// `Hidden::hide()`, so they do not end up owned by root.
+    /// Symlinks are not followed, only the link itself changes its owner.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | Supported | Error | Supported |
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use areia::User;
+    ///
+    /// if let Some(user) = User::invoking() {
+    ///     user.chown("/home/alice/.config/app/settings.toml").unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the user does not exist,
+    /// `AreiaError::OtherUsersNotSupported` on Windows or the OS error if changing the owner
+    /// failed, e.g. without root privileges.
+    pub fn chown<P: AsRef<Path>>(&self, path: P) -> AreiaResult<()> {
+        change_owner_to(path.as_ref(), self)
+    }
+}
+
+impl From<&str> for User {
+    fn from(name: &str) -> Self {
+        User::Name(name.to_owned())
+    }
+}
+
+impl From<String> for User {
+    fn from(name: String) -> Self {
+        User::Name(name)
+    }
+}
+
+impl From<u32> for User {
+    fn from(uid: u32) -> Self {
+        User::Uid(uid)
+    }
+}
+
+impl Display for User {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            User::Name(name) => write!(f, "{name}"),
+            User::Uid(uid) => write!(f, "uid {uid}"),
+        }
+    }
+}
+
+/// The entry of a user in the passwd database
+///
+/// Contains everything except the password, e.g. to show the display name or start the login
+/// shell of a user.
+/// Fields that are not valid UTF-8 are converted lossily.
+///
+/// | Linux | Windows | macOS |
+/// | --- | --- | --- |
+/// | Supported | Error | Supported |
+///
+/// # Example
+///
+/// ```
+/// use areia::UserInfo;
+///
+/// if cfg!(unix) {
+///     let root = UserInfo::lookup("root").unwrap();
+///     assert_eq!(root.uid(), 0);
+///     assert!(root.home().is_absolute());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserInfo {
+    name: String,
+    uid: u32,
+    gid: u32,
+    gecos: String,
+    home: PathBuf,
+    shell: PathBuf,
+}
+
+impl UserInfo {
+    pub(crate) fn new(
+        name: String,
+        uid: u32,
+        gid: u32,
+        gecos: String,
+        home: PathBuf,
+        shell: PathBuf,
+    ) -> UserInfo {
+        UserInfo {
+            name,
+            uid,
+            gid,
+            gecos,
+            home,
+            shell,
+        }
+    }
+
+    /// Look up the passwd entry of the current user
+    ///
+    /// The entry is looked up by the uid of the process, `$HOME` and `$SHELL` are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::UserInfo;
+    ///
+    /// if cfg!(unix) {
+    ///     let me = UserInfo::current().unwrap();
+    ///     assert!(!me.name().is_empty());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the current uid has no entry,
+    /// `AreiaError::OtherUsersNotSupported` on Windows or the OS error if the lookup failed
+    pub fn current() -> AreiaResult<UserInfo> {
+        get_current_user_info()
+    }
+
+    /// Look up the passwd entry of a user, addressed by name or uid
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::UserInfo;
+    ///
+    /// if cfg!(unix) {
+    ///     assert_eq!(UserInfo::lookup(0).unwrap().name(), "root");
+    ///     assert!(UserInfo::lookup("areia-no-such-user").is_err());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::UserNotFound` if the user does not exist,
+    /// `AreiaError::OtherUsersNotSupported` on Windows or the OS error if the lookup failed
+    pub fn lookup<U: Into<User>>(user: U) -> AreiaResult<UserInfo> {
+        get_user_info(&user.into())
+    }
+
+    /// The login name
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The user id
+    #[must_use]
+    pub fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    /// The id of the primary group
+    #[must_use]
+    pub fn gid(&self) -> u32 {
+        self.gid
+    }
+
+    /// The raw GECOS field, usually the full name followed by comma separated contact details
+    #[must_use]
+    pub fn gecos(&self) -> &str {
+        &self.gecos
+    }
+
+    /// The display name, the first entry of the GECOS field
+    ///
+    /// Returns `None` if the GECOS field has no name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::UserInfo;
+    ///
+    /// if let Ok(me) = UserInfo::current() {
+    ///     println!("Welcome, {}!", me.display_name().unwrap_or(me.name()));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn display_name(&self) -> Option<&str> {
+        self.gecos
+            .split(',')
+            .next()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+    }
+
+    /// The home directory
+    #[must_use]
+    pub fn home(&self) -> &PathBuf {
+        &self.home
+    }
+
+    /// The login shell
+    #[must_use]
+    pub fn shell(&self) -> &PathBuf {
+        &self.shell
+    }
+
+    /// The user as `User`, addressed by uid
+    #[must_use]
+    pub fn user(&self) -> User {
+        User::Uid(self.uid)
+    }
+}
+
+/// The environment of another user
+///
+/// Only the home directory is known, it is read from the passwd database.
+/// The environment variables of the user are not available, so the defaults are used.
+#[derive(Debug, Clone)]
+pub(crate) struct PasswdEnv {
+    home: PathBuf,
+}
+
+impl PasswdEnv {
+    pub(crate) fn new(user: &User) -> AreiaResult<PasswdEnv> {
+        Ok(PasswdEnv {
+            home: get_user_home(user)?,
+        })
+    }
+}
+
+impl EnvSource for PasswdEnv {
+    fn var_os(&self, _key: &str) -> Option<OsString> {
+        None
+    }
+
+    fn home_dir(&self) -> AreiaResult<PathBuf> {
+        Ok(self.home.clone())
+    }
+
+    fn home_dir_source(&self) -> DirSource {
+        DirSource::Passwd
+    }
+}
+
+/// A caller supplied environment
+///
+/// Only the variables added with `MapEnv::with_var()` are set, the process environment is never
+/// read.
+/// This allows resolving directories for arbitrary environments deterministically, e.g. in tests
+/// or multi-tenant services.
+#[derive(Debug, Clone, Default)]
+pub struct MapEnv {
+    home: PathBuf,
+    vars: HashMap<String, OsString>,
+}
+
+impl MapEnv {
+    /// Create a new, empty `MapEnv` with the supplied home directory
+    ///
+    /// The home directory must be absolute, otherwise `EnvSource::home_dir()` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{EnvSource, MapEnv};
+    ///
+    /// let env = MapEnv::new("/home/alice");
+    /// assert!(env.var_os("XDG_CONFIG_HOME").is_none());
+    /// ```
+    pub fn new<P: Into<PathBuf>>(home: P) -> MapEnv {
+        MapEnv {
+            home: home.into(),
+            vars: HashMap::new(),
+        }
+    }
+
+    /// Set the environment variable `key` to `value`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::{EnvSource, MapEnv};
+    ///
+    /// let env = MapEnv::new("/home/alice").with_var("XDG_CONFIG_HOME", "/etc/alice");
+    /// assert_eq!(env.var_os("XDG_CONFIG_HOME"), Some("/etc/alice".into()));
+    /// ```
+    #[must_use]
+    pub fn with_var<K: Into<String>, V: Into<OsString>>(mut self, key: K, value: V) -> MapEnv {
+        self.vars.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl EnvSource for MapEnv {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.vars.get(key).cloned()
+    }
+
+    /// Returns `AreiaError::CantGetHomeDir` if the supplied home directory is empty or relative,
+    /// e.g. for `MapEnv::default()`. On Windows a home without a drive, e.g. `\Users\alice`, is
+    /// accepted.
+    fn home_dir(&self) -> AreiaResult<PathBuf> {
+        if self.home.has_root() {
+            Ok(self.home.clone())
+        } else {
+            Err(AreiaError::CantGetHomeDir)
+        }
+    }
+}
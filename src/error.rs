@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crate::{DirKind, RuntimeDirViolation, User};
+
 pub type AreiaResult<T> = Result<T, AreiaError>;
 
 type WinErrString = String;
@@ -21,6 +23,18 @@ pub enum AreiaError {
     WindowsErrorPathDoesNotExist(PathBuf),
     MacError(MacErrString),
     HiddenFileInsideSystemDir(PathBuf),
+    DirectoryNotAvailable(DirKind),
+    WritingUserDirsNotSupported(Reason),
+    UnknownDirKind(String),
+    InvalidRuntimeDir(PathBuf, Vec<RuntimeDirViolation>),
+    UserNotFound(User),
+    OtherUsersNotSupported(Reason),
+    UndefinedVariable(String),
+    InvalidToken(String),
+    WatchingNotSupported(Reason),
+    NotADirectory(PathBuf),
+    InvalidProjectName(String),
+    DirectoryManagedBySystem(DirKind),
 }
 
 impl From<std::io::Error> for AreiaError {
@@ -61,6 +75,53 @@ impl std::fmt::Display for AreiaError {
             AreiaError::HiddenFileInsideSystemDir(path) => {
                 write!(f, "Hidden file inside system directory: {:?}", path)
             }
+            AreiaError::DirectoryNotAvailable(kind) => {
+                write!(f, "Directory not available on this OS: {kind}")
+            }
+            AreiaError::WritingUserDirsNotSupported(reason) => {
+                write!(
+                    f,
+                    "Writing user directories not supported. Reason: {reason}"
+                )
+            }
+            AreiaError::UnknownDirKind(name) => write!(f, "Unknown directory kind: {name}"),
+            AreiaError::InvalidRuntimeDir(path, violations) => {
+                let violations = violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "Invalid runtime directory {}: {violations}",
+                    path.display()
+                )
+            }
+            AreiaError::UserNotFound(user) => write!(f, "User not found: {user}"),
+            AreiaError::OtherUsersNotSupported(reason) => {
+                write!(f, "Resolving other users not supported. Reason: {reason}")
+            }
+            AreiaError::UndefinedVariable(name) => {
+                write!(f, "Environment variable is not set: {name}")
+            }
+            AreiaError::InvalidToken(token) => {
+                write!(f, "Token does not point inside its directory: {token}")
+            }
+            AreiaError::WatchingNotSupported(reason) => {
+                write!(f, "Watching not supported. Reason: {reason}")
+            }
+            AreiaError::InvalidProjectName(name) => {
+                write!(f, "Project name is no single file name: {name}")
+            }
+            AreiaError::DirectoryManagedBySystem(kind) => {
+                write!(
+                    f,
+                    "Directory is managed by the system and never created: {kind}"
+                )
+            }
+            AreiaError::NotADirectory(path) => {
+                write!(f, "Path is not a directory: {}", path.display())
+            }
         }
     }
 }
@@ -0,0 +1,272 @@
+use std::{
+    fmt::Display,
+    io::ErrorKind,
+    ops::{BitOr, BitOrAssign},
+    path::Path,
+};
+
+use crate::{
+    error::{AreiaError, AreiaResult},
+    utils::create_private_dir_all,
+};
+
+/// The first line of a `CACHEDIR.TAG`, see <https://bford.info/cachedir/>
+const CACHEDIR_SIGNATURE: &str = "Signature: 8a477f597d28d172789f06886806bc55";
+const CACHEDIR_TAG: &str = "CACHEDIR.TAG";
+const NO_MEDIA: &str = ".nomedia";
+const TRACKER_IGNORE: &str = ".trackerignore";
+const NO_SEARCH: &str = ".nosearch";
+
+/// A kind of tool that skips directories marked with `mark_excluded()`
+///
+/// | Kind | Marker files | Honored by |
+/// | --- | --- | --- |
+/// | `Backup` | `CACHEDIR.TAG` | `tar --exclude-caches`, borg, restic, Duplicity |
+/// | `Indexing` | `.trackerignore`, `.nosearch` | GNOME Tracker / `LocalSearch`, other desktop search engines |
+/// | `Media` | `.nomedia` | Android and desktop media scanners |
+///
+/// The markers are plain files, they work the same on every OS, but not every tool honors them,
+/// e.g. Time Machine and Spotlight on macOS do not.
+///
+/// # Example
+///
+/// ```
+/// use areia::{Exclude, Excludes};
+///
+/// let excludes = Exclude::Backup | Exclude::Media;
+/// assert!(excludes.contains(Exclude::Backup));
+/// assert!(!excludes.contains(Exclude::Indexing));
+/// assert_eq!(Exclude::Indexing.to_string(), "indexing");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exclude {
+    Backup,
+    Indexing,
+    Media,
+}
+
+impl Exclude {
+    /// Every kind
+    pub const ALL: [Exclude; 3] = [Exclude::Backup, Exclude::Indexing, Exclude::Media];
+
+    fn bit(self) -> u8 {
+        match self {
+            Exclude::Backup => 1,
+            Exclude::Indexing => 1 << 1,
+            Exclude::Media => 1 << 2,
+        }
+    }
+
+    /// Whether the marker of this kind is inside `dir`
+    fn is_marked(self, dir: &Path) -> bool {
+        match self {
+            Exclude::Backup => std::fs::read(dir.join(CACHEDIR_TAG))
+                .is_ok_and(|tag| tag.starts_with(CACHEDIR_SIGNATURE.as_bytes())),
+            Exclude::Indexing => {
+                dir.join(TRACKER_IGNORE).is_file() || dir.join(NO_SEARCH).is_file()
+            }
+            Exclude::Media => dir.join(NO_MEDIA).is_file(),
+        }
+    }
+
+    /// Writes the missing markers of this kind into `dir`, existing markers are kept
+    fn mark(self, dir: &Path) -> AreiaResult<()> {
+        match self {
+            Exclude::Backup => {
+                if !self.is_marked(dir) {
+                    std::fs::write(
+                        dir.join(CACHEDIR_TAG),
+                        format!(
+                            "{CACHEDIR_SIGNATURE}\n\
+                             # This file is a cache directory tag created by areia.\n\
+                             # For information about cache directory tags, see:\n\
+                             #\thttps://bford.info/cachedir/\n"
+                        ),
+                    )?;
+                }
+            }
+            Exclude::Indexing => {
+                create_marker(&dir.join(TRACKER_IGNORE))?;
+                create_marker(&dir.join(NO_SEARCH))?;
+            }
+            Exclude::Media => create_marker(&dir.join(NO_MEDIA))?,
+        }
+        Ok(())
+    }
+}
+
+impl Display for Exclude {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exclude::Backup => write!(f, "backup"),
+            Exclude::Indexing => write!(f, "indexing"),
+            Exclude::Media => write!(f, "media"),
+        }
+    }
+}
+
+/// A set of `Exclude` kinds, created by combining them with `|`
+///
+/// # Example
+///
+/// ```
+/// use areia::{Exclude, Excludes};
+///
+/// let mut excludes = Excludes::empty();
+/// assert!(excludes.is_empty());
+/// excludes |= Exclude::Indexing;
+/// assert_eq!(excludes, Excludes::from(Exclude::Indexing));
+/// assert_eq!(Excludes::all().iter().count(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Excludes(u8);
+
+impl Excludes {
+    /// The set without any kind
+    #[must_use]
+    pub fn empty() -> Excludes {
+        Excludes(0)
+    }
+
+    /// The set with every kind
+    #[must_use]
+    pub fn all() -> Excludes {
+        Exclude::ALL
+            .into_iter()
+            .fold(Excludes::empty(), |all, kind| all | kind)
+    }
+
+    /// Whether `kind` is part of the set
+    #[must_use]
+    pub fn contains(self, kind: Exclude) -> bool {
+        self.0 & kind.bit() != 0
+    }
+
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The kinds of the set, in the order of `Exclude::ALL`
+    pub fn iter(self) -> impl Iterator<Item = Exclude> {
+        Exclude::ALL
+            .into_iter()
+            .filter(move |kind| self.contains(*kind))
+    }
+}
+
+impl From<Exclude> for Excludes {
+    fn from(kind: Exclude) -> Self {
+        Excludes(kind.bit())
+    }
+}
+
+impl BitOr for Exclude {
+    type Output = Excludes;
+
+    fn bitor(self, rhs: Exclude) -> Excludes {
+        Excludes::from(self) | rhs
+    }
+}
+
+impl BitOr<Exclude> for Excludes {
+    type Output = Excludes;
+
+    fn bitor(self, rhs: Exclude) -> Excludes {
+        Excludes(self.0 | rhs.bit())
+    }
+}
+
+impl BitOr for Excludes {
+    type Output = Excludes;
+
+    fn bitor(self, rhs: Excludes) -> Excludes {
+        Excludes(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign<Exclude> for Excludes {
+    fn bitor_assign(&mut self, rhs: Exclude) {
+        *self = *self | rhs;
+    }
+}
+
+/// Marks the directory `path` so backup tools, desktop search or media scanners skip it
+///
+/// Writes the marker files of every kind in `excludes` into `path`, see `Exclude` for the
+/// files and the tools honoring them.
+/// The directory and any missing parents are created with the permission `0700` on Unix.
+/// Markers that are already there are kept, so marking a directory again does nothing.
+///
+/// # Example
+///
+/// ```
+/// use areia::{Exclude, excluded_from, mark_excluded};
+///
+/// let dir = std::env::temp_dir().join("areia-doc-mark-excluded");
+/// mark_excluded(&dir, Exclude::Backup | Exclude::Indexing).unwrap();
+/// assert!(dir.join("CACHEDIR.TAG").is_file());
+/// assert!(excluded_from(dir.join("thumbnails")).contains(Exclude::Backup));
+/// assert!(!excluded_from(&dir).contains(Exclude::Media));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns `AreiaError::NotADirectory` if `path` exists but is no directory, or an `AreiaError`
+/// if the directory or a marker could not be written
+pub fn mark_excluded<P: AsRef<Path>, E: Into<Excludes>>(path: P, excludes: E) -> AreiaResult<()> {
+    let path = path.as_ref();
+    match std::fs::metadata(path) {
+        Ok(metadata) if !metadata.is_dir() => {
+            return Err(AreiaError::NotADirectory(path.to_path_buf()));
+        }
+        Ok(_) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => create_private_dir_all(path)?,
+        Err(err) => return Err(err.into()),
+    }
+    for kind in excludes.into().iter() {
+        kind.mark(path)?;
+    }
+    Ok(())
+}
+
+/// The kinds `path` is excluded from by markers in itself or any of its ancestors
+///
+/// A marker applies to everything inside its directory, so a file inside a marked directory
+/// is excluded as well.
+/// A `CACHEDIR.TAG` only counts if it starts with the signature of the specification.
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, Exclude, excluded_from};
+///
+/// let base = BaseDirs::new().unwrap();
+/// let excludes = excluded_from(base.home_dir());
+/// assert!(!excludes.contains(Exclude::Backup));
+/// ```
+#[must_use]
+pub fn excluded_from<P: AsRef<Path>>(path: P) -> Excludes {
+    let mut excludes = Excludes::empty();
+    for dir in path.as_ref().ancestors() {
+        for kind in Exclude::ALL {
+            if !excludes.contains(kind) && kind.is_marked(dir) {
+                excludes |= kind;
+            }
+        }
+    }
+    excludes
+}
+
+/// Creates an empty marker file, an existing one is kept
+fn create_marker(path: &Path) -> AreiaResult<()> {
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+    {
+        Err(err) if err.kind() != ErrorKind::AlreadyExists => Err(err.into()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    BaseDirs, DirKind, EnvSource, ProcessEnv, User, UserDirs, base_dirs,
+    error::{AreiaError, AreiaResult},
+    user_dirs,
+    utils::get_user_home,
+};
+
+/// The XDG base directory variables, they are resolved like `BaseDirs` does
+const XDG_BASE_VARS: [(&str, DirKind); 6] = [
+    ("XDG_CACHE_HOME", DirKind::Cache),
+    ("XDG_CONFIG_HOME", DirKind::Config),
+    ("XDG_DATA_HOME", DirKind::Data),
+    ("XDG_STATE_HOME", DirKind::State),
+    ("XDG_BIN_HOME", DirKind::Executable),
+    ("XDG_RUNTIME_DIR", DirKind::Runtime),
+];
+
+/// Expands `~`, `~user` and environment variables in `path`, like a shell does
+///
+/// - `~` and `$HOME` are the home directory of `BaseDirs::home_dir()`, so in portable mode they
+///   are the portable root like the XDG base directories below
+/// - `~user` is the home directory of `user` from the passwd database
+/// - `$VAR` and `${VAR}` are the value of the environment variable `VAR`
+/// - The XDG base directory variables, e.g. `$XDG_CONFIG_HOME`, are resolved like `BaseDirs`
+///   does, so they expand to the default if they are unset or relative
+/// - The XDG user directory variables, e.g. `$XDG_DOWNLOAD_DIR`, are resolved like `UserDirs`
+///   does
+///
+/// A `$` that is not followed by a variable name is kept.
+/// Paths that are not valid UTF-8 are returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, expand_path};
+///
+/// let base = BaseDirs::new().unwrap();
+/// let path = expand_path("~/Downloads/foo").unwrap();
+/// assert_eq!(path, base.home_dir().join("Downloads/foo"));
+/// let path = expand_path("${XDG_CONFIG_HOME}/app").unwrap();
+/// assert_eq!(path, base.config_dir().join("app"));
+/// ```
+///
+/// # Errors
+///
+/// Returns `AreiaError::UndefinedVariable` if a variable is not set,
+/// `AreiaError::UserNotFound` if the user of `~user` does not exist or
+/// `AreiaError::DirectoryNotAvailable` if an XDG variable has no directory, e.g.
+/// `$XDG_RUNTIME_DIR` on macOS.
+pub fn expand_path<P: AsRef<Path>>(path: P) -> AreiaResult<PathBuf> {
+    expand(path.as_ref(), &ProcessEnv, true)
+}
+
+/// Expands `~`, `~user` and environment variables in `path` with the environment `env`
+///
+/// See `expand_path()` for the rules.
+///
+/// # Example
+///
+/// ```
+/// use areia::{MapEnv, expand_path_with};
+/// use std::path::PathBuf;
+///
+/// let env = MapEnv::new("/home/alice").with_var("PROJECT", "areia");
+/// let path = expand_path_with("~/src/$PROJECT", &env).unwrap();
+/// assert_eq!(path, PathBuf::from("/home/alice/src/areia"));
+/// ```
+///
+/// # Errors
+///
+/// See `expand_path()`.
+pub fn expand_path_with<P: AsRef<Path>>(path: P, env: &dyn EnvSource) -> AreiaResult<PathBuf> {
+    expand(path.as_ref(), env, false)
+}
+
+/// `process` resolves the XDG variables with the cached `base_dirs()` and `user_dirs()`, which
+/// also detect portable mode and Flatpak by their files
+fn expand(path: &Path, env: &dyn EnvSource, process: bool) -> AreiaResult<PathBuf> {
+    let Some(mut rest) = path.to_str() else {
+        return Ok(path.to_path_buf());
+    };
+    let mut out = OsString::new();
+
+    if let Some(tilde) = rest.strip_prefix('~') {
+        let end = tilde.find(std::path::is_separator).unwrap_or(tilde.len());
+        let (name, tail) = tilde.split_at(end);
+        if name.is_empty() {
+            out.push(base(env, process)?.home_dir());
+        } else {
+            out.push(get_user_home(&User::Name(name.to_owned()))?);
+        }
+        rest = tail;
+    }
+
+    while let Some(start) = rest.find('$') {
+        out.push(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, tail) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", after),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            after.split_at(end)
+        };
+        if name.is_empty() {
+            out.push("$");
+        } else {
+            out.push(variable(env, process, name)?);
+        }
+        rest = tail;
+    }
+    out.push(rest);
+    Ok(PathBuf::from(out))
+}
+
+/// Rewrites `path` to start with `~` if it is inside the home directory, e.g. for display
+///
+/// The inverse of the `~` expansion of `expand_path()`.
+/// Paths outside of the home directory are returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use areia::{BaseDirs, contract_path};
+/// use std::path::PathBuf;
+///
+/// let base = BaseDirs::new().unwrap();
+/// let path = contract_path(base.home_dir().join("Downloads/foo"));
+/// assert_eq!(path, PathBuf::from("~").join("Downloads/foo"));
+/// ```
+pub fn contract_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    contract(path.as_ref(), &ProcessEnv, true)
+}
+
+/// Rewrites `path` to start with `~` if it is inside the home directory of `env`
+///
+/// See `contract_path()`.
+///
+/// # Example
+///
+/// ```
+/// use areia::{MapEnv, contract_path_with};
+/// use std::path::PathBuf;
+///
+/// let env = MapEnv::new("/home/alice");
+/// assert_eq!(contract_path_with("/home/alice/notes", &env), PathBuf::from("~").join("notes"));
+/// assert_eq!(contract_path_with("/home/alicia", &env), PathBuf::from("/home/alicia"));
+/// ```
+pub fn contract_path_with<P: AsRef<Path>>(path: P, env: &dyn EnvSource) -> PathBuf {
+    contract(path.as_ref(), env, false)
+}
+
+fn contract(path: &Path, env: &dyn EnvSource, process: bool) -> PathBuf {
+    let Ok(base) = base(env, process) else {
+        return path.to_path_buf();
+    };
+    let home = base.home_dir();
+    // Every path is inside `/`, contracting it would only obscure the path
+    if home.parent().is_none() {
+        return path.to_path_buf();
+    }
+    match path.strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => PathBuf::from("~"),
+        Ok(rest) => Path::new("~").join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// The value of the variable `name`, directories are resolved like `BaseDirs` and `UserDirs`
+fn variable(env: &dyn EnvSource, process: bool, name: &str) -> AreiaResult<OsString> {
+    if name == "HOME" {
+        return Ok(base(env, process)?.home_dir().clone().into_os_string());
+    }
+    if let Some((_, kind)) = XDG_BASE_VARS.iter().find(|(var, _)| *var == name) {
+        return dir_value(base(env, process)?.get(*kind), *kind);
+    }
+    if let Some(kind) = name
+        .strip_prefix("XDG_")
+        .and_then(|key| key.strip_suffix("_DIR"))
+        .and_then(DirKind::from_xdg_key)
+    {
+        let user = if process {
+            user_dirs()?
+        } else {
+            Arc::new(UserDirs::from_env(env)?)
+        };
+        return dir_value(user.get(kind), kind);
+    }
+    env.var_os(name)
+        .ok_or_else(|| AreiaError::UndefinedVariable(name.to_owned()))
+}
+
+/// The `BaseDirs` of `env`, the cached ones for the process environment
+fn base(env: &dyn EnvSource, process: bool) -> AreiaResult<Arc<BaseDirs>> {
+    if process {
+        base_dirs()
+    } else {
+        Ok(Arc::new(BaseDirs::from_env(env)?))
+    }
+}
+
+fn dir_value(dir: Option<&Path>, kind: DirKind) -> AreiaResult<OsString> {
+    dir.map(|dir| dir.as_os_str().to_owned())
+        .ok_or(AreiaError::DirectoryNotAvailable(kind))
+}
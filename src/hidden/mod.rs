@@ -1,9 +1,33 @@
 mod normal_hide;
 mod super_hide;
-use std::path::PathBuf;
+use std::{ffi::OsStr, path::PathBuf};
 
 use crate::error::AreiaResult;
 
+/// Returns true if the single file or directory name `name` is hidden by the Unix convention
+///
+/// A name is hidden if it starts with a `.`, the special names `.` and `..` are never hidden.
+/// The check is purely lexical and works for names that are not valid UTF-8.
+///
+/// On Windows, hiding is an attribute of the file, not part of its name, use `Hidden::is_hidden()`
+/// for existing paths there.
+///
+/// # Example
+///
+/// ```
+/// use areia::is_hidden_name;
+/// use std::ffi::OsStr;
+///
+/// assert!(is_hidden_name(OsStr::new(".config")));
+/// assert!(!is_hidden_name(OsStr::new("notes.txt")));
+/// assert!(!is_hidden_name(OsStr::new(".")));
+/// assert!(!is_hidden_name(OsStr::new("..")));
+/// ```
+#[must_use]
+pub fn is_hidden_name(name: &OsStr) -> bool {
+    name != "." && name != ".." && name.as_encoded_bytes().starts_with(b".")
+}
+
 pub trait Hidden {
     /// Returns true if the path is hidden
     ///
@@ -11,7 +35,9 @@ pub trait Hidden {
     ///
     /// ## Unix
     ///
-    /// Checks if any component of the path is hidden (`.` prefix).
+    /// Checks if any component of the path is hidden (`.` prefix), see `is_hidden_name()`.
+    /// The path is normalized lexically first, so `./notes.txt` and `../shared/file` are not
+    /// hidden, and neither is `.cache/..`.
     /// Does not check if path exists.
     /// Returns `true` if any component is hidden.
     /// Returns `false` if any component is not hidden.
@@ -57,6 +83,7 @@ pub trait Hidden {
     /// If the path exists, the last directory or file in the path is moved to be hidden.
     /// This is done using 'atomic move' - ensuring no data is lost.
     /// If the path does not exist, it is created.
+    /// Names are handled as bytes, so names that are not valid UTF-8 are hidden as well.
     ///
     /// ## Windows
     ///
@@ -158,7 +185,8 @@ pub trait Hidden {
     ///
     /// # Errors
     ///
-    /// Errors if the program has insufficient permissions to move the file or folder.
+    /// Errors if the path has no file name to hide, e.g. ends in `..`, or the path can't be
+    /// hidden on this OS (Windows).
     fn try_into_hidden_path(&self) -> AreiaResult<PathBuf>;
 
     /// Like unhide, but returns only the unhidden path - no file deletion or moving whatsoever.
@@ -190,9 +218,9 @@ pub trait Hidden {
 pub trait SuperHidden {
     /// Checks if any component of the path is `super hidden`.
     /// The path must point to an existing file or directory.
-    /// This concept only applies to MacOS and Windows.
     ///
     /// `super hidden` is a file or directory that is hidden, as achieved with using `.hide()` provided by `areia`, and marked with a platform specific attribute or flag.
+    /// On Linux it is listed in the `.hidden` file of its parent directory instead, keeping its visible name.
     ///
     /// # Platform specific behaviour
     ///
@@ -200,7 +228,7 @@ pub trait SuperHidden {
     ///
     /// ### Linux
     ///
-    /// Always returns `AreiaError::SuperHidingNotSupported`.
+    /// Returns `true` if any component of the path is listed in the `.hidden` file of its parent directory.
     ///
     /// ### MacOS
     ///
@@ -229,9 +257,9 @@ pub trait SuperHidden {
     fn is_super_hidden(&self) -> AreiaResult<bool>;
     /// Super hides the file or directory pointed to by the path.
     /// The path must point to an existing file or directory.
-    /// This concept only applies to MacOS and Windows.
     ///
     /// `super hidden` is a file or directory that is hidden, as achieved with using `.hide()` provided by `areia`, and marked with a platform specific attribute or flag.
+    /// On Linux it is listed in the `.hidden` file of its parent directory instead, keeping its visible name.
     ///
     /// # Platform specific behaviour
     ///
@@ -239,7 +267,10 @@ pub trait SuperHidden {
     ///
     /// ### Linux
     ///
-    /// Always returns `AreiaError::SuperHidingNotSupported`.
+    /// Does nothing if the path itself is already listed, a listed parent does not count.
+    /// Otherwise, its name is added to the `.hidden` file of the parent directory, which is created if needed.
+    /// The file or directory is not renamed, the returned path is the same.
+    /// GNOME Files, Dolphin, Thunar and Nemo hide listed entries, `ls` and other tools still show them.
     ///
     /// ### MacOS
     ///
@@ -274,9 +305,9 @@ pub trait SuperHidden {
     /// Super un-hides the file or directory pointed to by the path.
     /// The reverse of `.super_hide()`.
     /// The path must point to an existing file or directory.
-    /// This concept only applies to MacOS and Windows.
     ///
     /// `super hidden` is a file or directory that is hidden, as achieved with using `.hide()` provided by `areia`, and marked with a platform specific attribute or flag.
+    /// On Linux it is listed in the `.hidden` file of its parent directory instead, keeping its visible name.
     ///
     /// # Platform specific behaviour
     ///
@@ -284,7 +315,9 @@ pub trait SuperHidden {
     ///
     /// ### Linux
     ///
-    /// Always returns `AreiaError::SuperHidingNotSupported`.
+    /// Does nothing if the file or directory is not listed.
+    /// Otherwise, the names of all listed components are removed from the `.hidden` files of their parent directories, each file is deleted once it is empty.
+    /// The file or directory is not renamed, the returned path is the same.
     ///
     /// ### MacOS
     ///
@@ -30,7 +30,7 @@ impl Hidden for PathBuf {
             "Unavailable on Windows".to_string(),
         ));
         #[cfg(not(target_os = "windows"))]
-        return Ok(make_hidden_path(&self));
+        return make_hidden_path(&self);
     }
 
     fn try_into_unhidden_path(&self) -> AreiaResult<PathBuf> {
@@ -3,17 +3,32 @@
 #![doc = include_str!("../README.md")]
 
 mod autos;
+mod cache;
 mod dirs;
+mod env;
+mod exclude;
+mod expand;
 mod hidden;
 mod utils;
+mod watch;
 
 pub mod error;
 
 // API
 
 // Directories
-pub use dirs::{BaseDirs, UserDirs};
+pub use dirs::{
+    BaseDirs, DirKind, DirSource, Explanation, PathTokens, ProjectDirs, RuntimeDirViolation,
+    Sandbox, SandboxKind, SystemDirs, UserDirs,
+};
+// Environment
+pub use env::{EnvSource, MapEnv, ProcessEnv, User, UserInfo};
 // Traits
-pub use hidden::{Hidden, SuperHidden};
+pub use hidden::{Hidden, SuperHidden, is_hidden_name};
+// Watching
+pub use watch::{LiveUserDirs, WatchEvent, Watcher};
 // Utilities
-pub use autos::{auto_creator, auto_deletor};
+pub use autos::{auto_creator, auto_creator_for, auto_deletor};
+pub use cache::{base_dirs, refresh, user_dirs};
+pub use exclude::{Exclude, Excludes, excluded_from, mark_excluded};
+pub use expand::{contract_path, contract_path_with, expand_path, expand_path_with};
@@ -1,115 +1,260 @@
 use std::{
-    collections::HashMap, env, ffi::OsString, fs::File, io::Read, os::unix::ffi::OsStringExt,
-    path::PathBuf,
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    fs::{self, File},
+    io::{ErrorKind, Read, Write},
+    os::unix::ffi::{OsStrExt, OsStringExt},
+    path::{Path, PathBuf},
 };
 
+use super::UsrDirs;
 use crate::{
+    DirKind, DirSource, EnvSource,
     error::{AreiaError, AreiaResult},
-    utils::unix::is_absolute_path,
+    utils::{create_private_dir_all, unix::is_absolute_path},
 };
 
-pub fn cache_dir(home: PathBuf) -> PathBuf {
-    if let Some(path) = env::var_os("XDG_CACHE_HOME").and_then(|x| is_absolute_path(x)) {
-        path
-    } else {
-        if home.join(".cache").exists() {
-            home.join(".cache")
-        } else {
-            // Judging by the `directories` source code, `home.join` should always return a valid path
-            unreachable!("Cache directory could not be found!");
-        }
-    }
+pub fn cache_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    env.var_os("XDG_CACHE_HOME")
+        .and_then(is_absolute_path)
+        .unwrap_or_else(|| home.join(".cache"))
 }
 
-pub fn config_dir(home: PathBuf) -> PathBuf {
-    if let Some(path) = env::var_os("XDG_CONFIG_HOME").and_then(|x| is_absolute_path(x)) {
-        path
-    } else {
-        if home.join(".config").exists() {
-            home.join(".config")
-        } else {
-            // Judging by the `directories` source code, `home.join` should always return a valid path
-            unreachable!("Config directory could not be found!");
-        }
-    }
+pub fn config_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    env.var_os("XDG_CONFIG_HOME")
+        .and_then(is_absolute_path)
+        .unwrap_or_else(|| home.join(".config"))
 }
 
-pub fn config_local_dir(home: PathBuf) -> PathBuf {
-    config_dir(home)
+pub fn config_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    config_dir(env, home)
 }
 
-pub fn data_local_dir(home: PathBuf) -> PathBuf {
-    data_dir(home)
+pub fn data_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    data_dir(env, home)
 }
 
-pub fn preference_dir(home: PathBuf) -> PathBuf {
-    config_dir(home)
+pub fn preference_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    config_dir(env, home)
 }
 
-pub fn data_dir(home: PathBuf) -> PathBuf {
-    if let Some(path) = env::var_os("XDG_DATA_HOME").and_then(|x| is_absolute_path(x)) {
-        path
-    } else {
-        if home.join(".local/share").exists() {
-            home.join(".local/share")
-        } else {
-            // Judging by the `directories` source code, `home.join` should always return a valid path
-            unreachable!("Data directory could not be found!");
-        }
-    }
+pub fn data_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    env.var_os("XDG_DATA_HOME")
+        .and_then(is_absolute_path)
+        .unwrap_or_else(|| home.join(".local/share"))
 }
 
-pub fn runtime_dir() -> Option<PathBuf> {
-    env::var_os("XDG_RUNTIME_DIR").and_then(|x| is_absolute_path(x))
+pub fn runtime_dir(env: &dyn EnvSource) -> Option<PathBuf> {
+    env.var_os("XDG_RUNTIME_DIR").and_then(is_absolute_path)
 }
 
-pub fn state_dir(home: PathBuf) -> Option<PathBuf> {
-    if let Some(path) = env::var_os("XDG_STATE_HOME").and_then(|x| is_absolute_path(x)) {
-        Some(path)
-    } else {
-        if home.join(".local/state").exists() {
-            Some(home.join(".local/state"))
-        } else {
-            // Judging by the `directories` source code, `home.join` should always return a valid path
-            unreachable!("State directory could not be found!");
-        }
+#[allow(clippy::unnecessary_wraps)]
+pub fn state_dir(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    Some(
+        env.var_os("XDG_STATE_HOME")
+            .and_then(is_absolute_path)
+            .unwrap_or_else(|| home.join(".local/state")),
+    )
+}
+
+pub fn executable_dir(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    env.var_os("XDG_BIN_HOME")
+        .and_then(is_absolute_path)
+        .or_else(|| Some(home.join(".local/bin")).filter(|path| path.exists()))
+}
+
+/// The environment variable a base directory is read from
+fn xdg_var(kind: DirKind) -> Option<&'static str> {
+    match kind {
+        DirKind::Cache => Some("XDG_CACHE_HOME"),
+        DirKind::Config | DirKind::ConfigLocal | DirKind::Preference => Some("XDG_CONFIG_HOME"),
+        DirKind::Data | DirKind::DataLocal => Some("XDG_DATA_HOME"),
+        DirKind::Executable => Some("XDG_BIN_HOME"),
+        DirKind::Runtime => Some("XDG_RUNTIME_DIR"),
+        DirKind::State => Some("XDG_STATE_HOME"),
+        _ => None,
     }
 }
 
-pub fn executable_dir(home: PathBuf) -> Option<PathBuf> {
-    if let Some(path) = env::var_os("XDG_BIN_HOME").and_then(|x| is_absolute_path(x)) {
-        Some(path)
-    } else {
-        if home.join(".local/bin").exists() {
-            Some(home.join(".local/bin"))
-        } else {
-            None
-        }
+/// Explains where a base directory comes from, including a set but ignored relative value
+pub fn base_dir_source(
+    env: &dyn EnvSource,
+    kind: DirKind,
+) -> (DirSource, Option<(String, OsString)>) {
+    let Some(var) = xdg_var(kind) else {
+        return (DirSource::Default, None);
+    };
+    match env.var_os(var) {
+        Some(value) if Path::new(&value).is_absolute() => (DirSource::EnvVar(var.to_owned()), None),
+        // An empty variable is treated as unset by the spec
+        Some(value) if !value.is_empty() => (DirSource::Default, Some((var.to_owned(), value))),
+        _ => (DirSource::Default, None),
     }
 }
 
-fn font_dir(home: PathBuf) -> Option<PathBuf> {
-    if home.join(".local/share/fonts").exists() {
-        Some(home.join(".local/share/fonts"))
+pub fn data_dirs(env: &dyn EnvSource) -> Vec<PathBuf> {
+    search_dirs(env, "XDG_DATA_DIRS", &["/usr/local/share", "/usr/share"])
+}
+
+pub fn config_dirs(env: &dyn EnvSource) -> Vec<PathBuf> {
+    search_dirs(env, "XDG_CONFIG_DIRS", &["/etc/xdg"])
+}
+
+/// Splits a colon separated search path, dropping every entry that is not absolute.
+/// Falls back to `defaults` if the variable is unset, empty or contains no absolute entry.
+fn search_dirs(env: &dyn EnvSource, var: &str, defaults: &[&str]) -> Vec<PathBuf> {
+    let dirs: Vec<PathBuf> = env
+        .var_os(var)
+        .map(|value| {
+            std::env::split_paths(&value)
+                .filter_map(|path| is_absolute_path(path.into_os_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if dirs.is_empty() {
+        defaults.iter().map(PathBuf::from).collect()
     } else {
-        None
+        dirs
     }
 }
 
-pub fn get_usr_dirs<P: Into<PathBuf>>(home: P) -> AreiaResult<HashMap<String, Option<PathBuf>>> {
+fn font_dir(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    let out = data_dir(env, home).join("fonts");
+    if out.exists() { Some(out) } else { None }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_root_dir() -> AreiaResult<PathBuf> {
+    Ok(PathBuf::from("/"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_bin_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/usr/bin"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_local_bin_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/usr/local/bin"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_data_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/var/lib"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_share_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/usr/share"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_config_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/etc"))
+}
+
+/// Linux applications use a single lower-kebab-case directory name, e.g. `my-app`
+pub fn project_path(_qualifier: &str, _organization: &str, application: &str) -> PathBuf {
+    PathBuf::from(
+        application
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join("-"),
+    )
+}
+
+/// Every project directory kind has a base directory of its own
+pub fn project_subdir(_kind: DirKind) -> Option<&'static str> {
+    None
+}
+
+/// The user directories of the XDG spec and their conventional name inside the home directory
+const USER_DIRS: [(DirKind, &str); 8] = [
+    (DirKind::Desktop, "Desktop"),
+    (DirKind::Document, "Documents"),
+    (DirKind::Download, "Downloads"),
+    (DirKind::Audio, "Music"),
+    (DirKind::Picture, "Pictures"),
+    (DirKind::Public, "Public"),
+    (DirKind::Template, "Templates"),
+    (DirKind::Video, "Videos"),
+];
+
+/// Reads the `user-dirs.dirs` file inside the config directory of the user.
+/// If it does not exist, the first system wide `user-dirs.defaults` found in `$XDG_CONFIG_DIRS`
+/// is used, with the conventional names as last resort.
+pub fn get_usr_dirs<P: Into<PathBuf>>(env: &dyn EnvSource, home: P) -> AreiaResult<UsrDirs> {
     let home = home.into();
-    let usr_xdg_file = home.clone().join(".config").join("user-dirs.dirs");
-    match read_to_byte_vec(usr_xdg_file) {
+    let usr_xdg_file = user_dirs_file(env, home.clone()).expect("Always some on Linux");
+    let (mut dirs, entries, source) = match read_to_byte_vec(&usr_xdg_file) {
         Ok(bytes) => {
-            let mut out = parse_xdg_dirs(bytes, home.clone())?;
-            out.insert("FONTS".to_owned(), font_dir(home));
-            Ok(out)
+            let source = DirSource::UserDirsFile(usr_xdg_file);
+            // Standard directories without an entry are disabled
+            let dirs: HashMap<_, _> = USER_DIRS
+                .iter()
+                .map(|(kind, _)| (*kind, (None, source.clone())))
+                .collect();
+            (dirs, parse_xdg_dirs(bytes, home.clone())?, source)
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            let dirs = USER_DIRS
+                .iter()
+                .map(|(kind, name)| (*kind, (Some(home.join(name)), DirSource::Default)))
+                .collect();
+            let (defaults_file, entries) = system_usr_dirs_defaults(env, &home);
+            (dirs, entries, DirSource::SystemDefaults(defaults_file))
         }
-        Err(err) => Err(AreiaError::IoError(err)),
+        Err(err) => return Err(AreiaError::IoError(err)),
+    };
+
+    let mut custom = BTreeMap::new();
+    for (key, path) in entries {
+        match DirKind::from_xdg_key(&key) {
+            Some(kind) => {
+                dirs.insert(kind, (path, source.clone()));
+            }
+            None => {
+                if let Some(path) = path {
+                    custom.insert(key, path);
+                }
+            }
+        }
+    }
+
+    // Common extras without an entry in `user-dirs.dirs`, screenshot tools (e.g. GNOME) default to
+    //      a sub directory of the pictures
+    if !dirs.contains_key(&DirKind::Screenshot) {
+        let screenshots = dirs
+            .get(&DirKind::Picture)
+            .and_then(|(pictures, _)| pictures.as_ref())
+            .map(|pictures| pictures.join("Screenshots"));
+        dirs.insert(DirKind::Screenshot, (screenshots, DirSource::Default));
     }
+    dirs.entry(DirKind::Project)
+        .or_insert((Some(home.join("Projects")), DirSource::Default));
+    dirs.insert(DirKind::Font, (font_dir(env, home), DirSource::Default));
+    Ok(UsrDirs { dirs, custom })
 }
 
-fn read_to_byte_vec(path: PathBuf) -> std::io::Result<Vec<u8>> {
+/// Reads the first system wide `user-dirs.defaults` of `$XDG_CONFIG_DIRS`.
+/// The system defaults are optional - an unreadable file is treated like a missing one
+fn system_usr_dirs_defaults(
+    env: &dyn EnvSource,
+    home: &Path,
+) -> (PathBuf, HashMap<String, Option<PathBuf>>) {
+    config_dirs(env)
+        .into_iter()
+        .map(|dir| dir.join("user-dirs.defaults"))
+        .find_map(|file| {
+            read_to_byte_vec(&file)
+                .ok()
+                .map(|bytes| (file, parse_xdg_defaults(&bytes, home)))
+        })
+        .unwrap_or_default()
+}
+
+fn read_to_byte_vec(path: &Path) -> std::io::Result<Vec<u8>> {
     let mut file = File::open(path)?;
     // 1024 is used by `directories` - on my machine the file is 633 bytes; seems prudent to use a
     //      somewhat larger buffer
@@ -118,6 +263,39 @@ fn read_to_byte_vec(path: PathBuf) -> std::io::Result<Vec<u8>> {
     Ok(bytes)
 }
 
+/// Parses the `KEY=value` lines of a `user-dirs.defaults` file.
+/// Values are relative to the home directory, pointing a directory to the home directory
+/// disables it.
+fn parse_xdg_defaults(bytes: &[u8], home: &Path) -> HashMap<String, Option<PathBuf>> {
+    let mut out = HashMap::new();
+    for line in bytes.split(|x| *x == b'\n') {
+        let line = line.trim_ascii();
+        if line.starts_with(b"#") {
+            continue;
+        }
+        let Some((key, val)) = split_once(line, b'=') else {
+            continue;
+        };
+        let Ok(key) = str::from_utf8(key.trim_ascii()) else {
+            continue;
+        };
+        let mut val = val.trim_ascii();
+        if val.len() >= 2 && val.starts_with(b"\"") && val.ends_with(b"\"") {
+            val = &val[1..val.len() - 1];
+        }
+        let val = val.strip_prefix(b"$HOME").unwrap_or(val);
+        let val = val.strip_prefix(b"/").unwrap_or(val);
+
+        let path = if val.is_empty() {
+            None
+        } else {
+            Some(home.join(OsString::from_vec(val.to_vec())))
+        };
+        out.insert(key.to_owned(), path);
+    }
+    out
+}
+
 fn split_once(bytes: &[u8], sep: u8) -> Option<(&[u8], &[u8])> {
     for (i, byte) in bytes.iter().enumerate() {
         if *byte == sep {
@@ -128,46 +306,19 @@ fn split_once(bytes: &[u8], sep: u8) -> Option<(&[u8], &[u8])> {
 }
 fn parse_xdg_dirs(bytes: Vec<u8>, home: PathBuf) -> AreiaResult<HashMap<String, Option<PathBuf>>> {
     let mut out = HashMap::new();
-    let defaults = vec![
-        ("MUSIC".to_owned(), None),
-        ("DESKTOP".to_owned(), None),
-        ("DOCUMENTS".to_owned(), None),
-        ("DOWNLOADS".to_owned(), None),
-        ("PICTURES".to_owned(), None),
-        ("FONTS".to_owned(), None),
-        ("PUBLICSHARE".to_owned(), None),
-        ("TEMPLATES".to_owned(), None),
-        ("VIDEOS".to_owned(), None),
-    ];
-
-    for (key, val) in defaults {
-        out.insert(key, val);
-    }
 
     // We always continue (fail forwards) as not all lines are valid user dirs - there is normal
     //      text in there
     for line in bytes.split(|x| *x == b'\n') {
-        let (key, val) = match split_once(line, b'=') {
-            Some((key, val)) => (key, val),
-            None => continue,
+        let Some((key, val)) = xdg_dir_assignment(line) else {
+            continue;
         };
-
-        let key = key.trim_ascii();
-        let key = if key.starts_with(b"XDG_") && key.ends_with(b"_DIR") {
-            match str::from_utf8(&key[4..key.len() - 4]) {
-                Ok(key) => {
-                    // Mac and Win call it `DOWNLOADS`, XDG uses the singular - So we unify the names here
-                    if key == "DOWNLOAD" { "DOWNLOADS" } else { key }
-                }
-                // Err should never match, but just in case
-                Err(_) => continue,
-            }
-        } else {
+        let Ok(key) = str::from_utf8(key) else {
+            // Should never happen, but just in case
             continue;
         };
 
-        let val = val.trim_ascii();
-        let mut val = if val.starts_with(b"\"") && val.ends_with(b"\"") {
+        let mut val = if val.len() >= 2 && val.starts_with(b"\"") && val.ends_with(b"\"") {
             &val[1..val.len() - 1]
         } else {
             continue;
@@ -176,6 +327,7 @@ fn parse_xdg_dirs(bytes: Vec<u8>, home: PathBuf) -> AreiaResult<HashMap<String,
         let path_is_relative = if val == b"$HOME/" {
             // "To disable a directory, point it to the homedir"
             // XDG doc
+            out.insert(key.to_owned(), None);
             continue;
         } else if val.starts_with(b"$HOME/") {
             val = &val[b"$HOME/".len()..];
@@ -186,8 +338,7 @@ fn parse_xdg_dirs(bytes: Vec<u8>, home: PathBuf) -> AreiaResult<HashMap<String,
             continue;
         };
 
-        // Still need to remove all b'\\' from val (escaped characters)
-        let val = OsString::from_vec(val.iter().copied().filter(|x| *x != b'\\').collect());
+        let val = OsString::from_vec(unescape(val));
 
         let path = if path_is_relative {
             let mut path = home.clone();
@@ -203,45 +354,199 @@ fn parse_xdg_dirs(bytes: Vec<u8>, home: PathBuf) -> AreiaResult<HashMap<String,
     Ok(out)
 }
 
+/// Splits a `XDG_xxx_DIR=value` line into `xxx` and the trimmed value
+fn xdg_dir_assignment(line: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (key, val) = split_once(line, b'=')?;
+    let key = key.trim_ascii();
+    if key.len() > b"XDG__DIR".len() && key.starts_with(b"XDG_") && key.ends_with(b"_DIR") {
+        Some((&key[4..key.len() - 4], val.trim_ascii()))
+    } else {
+        None
+    }
+}
+
+/// Removes the shell escaping of a double quoted value, `\x` becomes `x`
+fn unescape(val: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(val.len());
+    let mut bytes = val.iter();
+    while let Some(byte) = bytes.next() {
+        if *byte == b'\\' {
+            if let Some(escaped) = bytes.next() {
+                out.push(*escaped);
+            }
+        } else {
+            out.push(*byte);
+        }
+    }
+    out
+}
+
+/// Escapes all characters with a special meaning inside double quotes of a shell
+fn escape(val: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(val.len());
+    for byte in val {
+        if matches!(byte, b'"' | b'\\' | b'$' | b'`') {
+            out.push(b'\\');
+        }
+        out.push(*byte);
+    }
+    out
+}
+
+/// Formats a `XDG_xxx_DIR="value"` line, paths inside the home directory are written relative to
+/// `$HOME`, `None` disables the directory by pointing it to the home directory
+fn xdg_dir_line(key: &str, path: Option<&Path>, home: &Path) -> Vec<u8> {
+    let mut out = format!("XDG_{key}_DIR=\"").into_bytes();
+    match path.map(|path| (path, path.strip_prefix(home))) {
+        None => out.extend_from_slice(b"$HOME/"),
+        Some((_, Ok(relative))) => {
+            out.extend_from_slice(b"$HOME/");
+            out.extend(escape(relative.as_os_str().as_bytes()));
+        }
+        Some((path, Err(_))) => out.extend(escape(path.as_os_str().as_bytes())),
+    }
+    out.push(b'"');
+    out
+}
+
+const USER_DIRS_HEADER: &[u8] =
+    b"# If you want to change or add directories, just edit the line you're
+# interested in. All local changes will be retained on the next run.
+# Format is XDG_xxx_DIR=\"$HOME/yyy\", where yyy is a shell-escaped
+# homedir-relative path, or XDG_xxx_DIR=\"/yyy\", where /yyy is an
+# absolute path. No other format is supported.
+#
+";
+
+/// Sets the user directories in the `user-dirs.dirs` file at `file`.
+/// Existing entries are replaced in place, all other lines and comments are preserved.
+/// The file is written to a temporary file first and then renamed, so readers never see a
+/// partially written file. The mode of an existing file is kept.
+/// Paths containing a line break can't be written and are rejected.
+pub fn write_usr_dirs(file: &Path, home: &Path, dirs: &[(&str, Option<&Path>)]) -> AreiaResult<()> {
+    // A line break would end the entry early and could inject further entries
+    if let Some(path) = dirs.iter().filter_map(|(_, path)| *path).find(|path| {
+        path.as_os_str()
+            .as_bytes()
+            .iter()
+            .any(|byte| matches!(byte, b'\n' | b'\r'))
+    }) {
+        return Err(AreiaError::WritingUserDirsNotSupported(format!(
+            "{} contains a line break",
+            path.display()
+        )));
+    }
+
+    let old = match read_to_byte_vec(file) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == ErrorKind::NotFound => USER_DIRS_HEADER.to_vec(),
+        Err(err) => return Err(AreiaError::IoError(err)),
+    };
+
+    let mut written = vec![false; dirs.len()];
+    let mut out = Vec::with_capacity(old.len() + dirs.len() * 64);
+    let old = old.strip_suffix(b"\n").unwrap_or(&old);
+    for line in old.split(|x| *x == b'\n') {
+        let entry = xdg_dir_assignment(line)
+            .and_then(|(key, _)| dirs.iter().position(|(name, _)| name.as_bytes() == key));
+        match entry {
+            // Duplicate entries are dropped, the last one would win anyway
+            Some(i) if written[i] => continue,
+            Some(i) => {
+                let (key, path) = dirs[i];
+                out.extend(xdg_dir_line(key, path, home));
+                written[i] = true;
+            }
+            None => out.extend_from_slice(line),
+        }
+        out.push(b'\n');
+    }
+    for ((key, path), written) in dirs.iter().zip(written) {
+        if !written {
+            out.extend(xdg_dir_line(key, *path, home));
+            out.push(b'\n');
+        }
+    }
+
+    if let Some(parent) = file.parent() {
+        create_private_dir_all(parent)?;
+    }
+    let mut tmp_name = file.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp = file.with_file_name(tmp_name);
+    // The replacement keeps the mode of the current file
+    let permissions = fs::metadata(file).ok().map(|meta| meta.permissions());
+    let result = File::create(&tmp)
+        .and_then(|mut tmp_file| {
+            if let Some(permissions) = permissions {
+                tmp_file.set_permissions(permissions)?;
+            }
+            tmp_file.write_all(&out)?;
+            tmp_file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp, file));
+    if let Err(err) = result {
+        let _ = fs::remove_file(&tmp);
+        return Err(AreiaError::IoError(err));
+    }
+    Ok(())
+}
+
+/// The `user-dirs.dirs` file inside the config directory of the user
+pub fn user_dirs_file(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    Some(config_dir(env, home).join("user-dirs.dirs"))
+}
+
 #[test]
 fn xdg_user_dir_parsing() {
-    let home = env::var_os("HOME").unwrap();
+    let env = crate::ProcessEnv;
+    let home = std::env::var_os("HOME").unwrap();
     let home_path = PathBuf::from(home.clone());
 
-    let dirs = get_usr_dirs(home);
-    if dirs.is_err() {
+    if !user_dirs_file(&env, home_path.clone()).is_some_and(|file| file.exists()) {
         // Home-lab does not have a `user-dirs.dirs` file
         // So for the CI we just skip the test like this
         return;
     }
-    let dirs = dirs.unwrap();
-    assert_eq!(dirs.len(), 9);
+    let dirs: HashMap<DirKind, Option<PathBuf>> = get_usr_dirs(&env, home)
+        .unwrap()
+        .dirs
+        .into_iter()
+        .map(|(kind, (val, _))| (kind, val))
+        .collect();
+    assert_eq!(dirs.len(), 11);
 
     assert_eq!(
-        dirs.get("DESKTOP").unwrap(),
+        dirs.get(&DirKind::Desktop).unwrap(),
         &Some(home_path.join("Desktop"))
     );
     assert_eq!(
-        dirs.get("DOCUMENTS").unwrap(),
+        dirs.get(&DirKind::Document).unwrap(),
         &Some(home_path.join("Documents"))
     );
     assert_eq!(
-        dirs.get("DOWNLOADS").unwrap(),
+        dirs.get(&DirKind::Download).unwrap(),
         &Some(home_path.join("Downloads"))
     );
-    assert_eq!(dirs.get("MUSIC").unwrap(), &Some(home_path.join("Music")));
     assert_eq!(
-        dirs.get("PICTURES").unwrap(),
+        dirs.get(&DirKind::Audio).unwrap(),
+        &Some(home_path.join("Music"))
+    );
+    assert_eq!(
+        dirs.get(&DirKind::Picture).unwrap(),
         &Some(home_path.join("Pictures"))
     );
     assert_eq!(
-        dirs.get("PUBLICSHARE").unwrap(),
+        dirs.get(&DirKind::Public).unwrap(),
         &Some(home_path.join("Public"))
     );
     assert_eq!(
-        dirs.get("TEMPLATES").unwrap(),
+        dirs.get(&DirKind::Template).unwrap(),
         &Some(home_path.join("Templates"))
     );
-    assert_eq!(dirs.get("VIDEOS").unwrap(), &Some(home_path.join("Videos")));
-    assert!(dirs.get("FONTS").unwrap().is_some());
+    assert_eq!(
+        dirs.get(&DirKind::Video).unwrap(),
+        &Some(home_path.join("Videos"))
+    );
+    assert!(dirs.get(&DirKind::Font).unwrap().is_some());
 }
@@ -1,56 +1,57 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
-use crate::error::{AreiaError, AreiaResult};
+use super::UsrDirs;
+use crate::{
+    DirKind, DirSource, EnvSource,
+    error::{AreiaError, AreiaResult},
+};
 
-pub fn cache_dir(home: PathBuf) -> PathBuf {
-    let out = home.join("Library").join("Caches");
-    if out.exists() {
-        out
-    } else {
-        unreachable!("cache dir does not exist")
-    }
+pub fn cache_dir(_env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    home.join("Library").join("Caches")
 }
 
-pub fn config_dir(home: PathBuf) -> PathBuf {
-    let out = home.join("Library").join("Application Support");
-    if out.exists() {
-        out
-    } else {
-        unreachable!("config dir does not exist")
-    }
+pub fn config_dir(_env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    home.join("Library").join("Application Support")
 }
 
-pub fn config_local_dir(home: PathBuf) -> PathBuf {
-    config_dir(home)
+pub fn config_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    config_dir(env, home)
 }
 
-pub fn data_dir(home: PathBuf) -> PathBuf {
-    config_dir(home)
+pub fn data_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    config_dir(env, home)
 }
 
-pub fn data_local_dir(home: PathBuf) -> PathBuf {
-    config_dir(home)
+pub fn data_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    config_dir(env, home)
 }
 
-pub fn runtime_dir() -> Option<PathBuf> {
+pub fn runtime_dir(_env: &dyn EnvSource) -> Option<PathBuf> {
     None
 }
 
-pub fn state_dir(_home: PathBuf) -> Option<PathBuf> {
+pub fn state_dir(_env: &dyn EnvSource, _home: PathBuf) -> Option<PathBuf> {
     None
 }
 
-pub fn executable_dir(_home: PathBuf) -> Option<PathBuf> {
+pub fn executable_dir(_env: &dyn EnvSource, _home: PathBuf) -> Option<PathBuf> {
     None
 }
 
-pub fn preference_dir(home: PathBuf) -> PathBuf {
-    let out = home.join("Library").join("Preferences");
-    if out.exists() {
-        out
-    } else {
-        unreachable!("preference dir does not exist")
-    }
+pub fn preference_dir(_env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    home.join("Library").join("Preferences")
+}
+
+pub fn data_dirs(_env: &dyn EnvSource) -> Vec<PathBuf> {
+    vec![PathBuf::from("/Library/Application Support")]
+}
+
+pub fn config_dirs(_env: &dyn EnvSource) -> Vec<PathBuf> {
+    vec![PathBuf::from("/Library/Application Support")]
 }
 
 fn font_dir(home: PathBuf) -> Option<PathBuf> {
@@ -58,7 +59,62 @@ fn font_dir(home: PathBuf) -> Option<PathBuf> {
     if out.exists() { Some(out) } else { None }
 }
 
-pub fn get_usr_dirs<P: Into<PathBuf>>(home: P) -> AreiaResult<HashMap<String, Option<PathBuf>>> {
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_root_dir() -> AreiaResult<PathBuf> {
+    Ok(PathBuf::from("/"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_bin_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/usr/bin"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_local_bin_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/usr/local/bin"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_data_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/Library/Application Support"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_share_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/usr/share"))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn system_config_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("/Library/Preferences"))
+}
+
+/// macOS applications use a reverse-DNS style bundle identifier, e.g. `com.Org.App`
+pub fn project_path(qualifier: &str, organization: &str, application: &str) -> PathBuf {
+    PathBuf::from(
+        [qualifier, organization, application]
+            .iter()
+            .map(|part| part.split_whitespace().collect::<Vec<_>>().join("-"))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+/// Every project directory kind has a base directory of its own
+pub fn project_subdir(_kind: DirKind) -> Option<&'static str> {
+    None
+}
+
+/// The base directories are fixed, no environment variable is read
+pub fn base_dir_source(
+    _env: &dyn EnvSource,
+    _kind: DirKind,
+) -> (DirSource, Option<(String, OsString)>) {
+    (DirSource::Default, None)
+}
+
+pub fn get_usr_dirs<P: Into<PathBuf>>(_env: &dyn EnvSource, home: P) -> AreiaResult<UsrDirs> {
     let home = home.into();
     let mut out = HashMap::new();
     let music = home.join("Music");
@@ -90,14 +146,39 @@ pub fn get_usr_dirs<P: Into<PathBuf>>(home: P) -> AreiaResult<HashMap<String, Op
         AreiaError::MacError("Movies directory does not exist".to_owned());
     }
 
-    out.insert("MUSIC".to_owned(), Some(music));
-    out.insert("DESKTOP".to_owned(), Some(desktop));
-    out.insert("DOCUMENTS".to_owned(), Some(documents));
-    out.insert("DOWNLOADS".to_owned(), Some(downloads));
-    out.insert("PICTURES".to_owned(), Some(pictures));
-    out.insert("PUBLICSHARE".to_owned(), Some(public));
-    out.insert("VIDEOS".to_owned(), Some(movies));
-    out.insert("FONTS".to_owned(), font_dir(home));
-    out.insert("TEMPLATES".to_owned(), None);
-    Ok(out)
+    out.insert(DirKind::Audio, Some(music));
+    out.insert(DirKind::Desktop, Some(desktop));
+    out.insert(DirKind::Document, Some(documents));
+    out.insert(DirKind::Download, Some(downloads));
+    out.insert(DirKind::Picture, Some(pictures));
+    out.insert(DirKind::Public, Some(public));
+    out.insert(DirKind::Video, Some(movies));
+    out.insert(DirKind::Font, font_dir(home.clone()));
+    out.insert(DirKind::Template, None);
+    // `screencapture` saves to the desktop unless configured otherwise
+    out.insert(DirKind::Screenshot, Some(home.join("Desktop")));
+    // Neither Finder nor Xcode suggest a location, so it is left to the user
+    out.insert(DirKind::Project, None);
+    Ok(UsrDirs {
+        dirs: out
+            .into_iter()
+            .map(|(kind, val)| (kind, (val, DirSource::Default)))
+            .collect(),
+        custom: BTreeMap::new(),
+    })
+}
+
+/// macOS has no `user-dirs.dirs` file, the user directories are fixed
+pub fn user_dirs_file(_env: &dyn EnvSource, _home: PathBuf) -> Option<PathBuf> {
+    None
+}
+
+pub fn write_usr_dirs(
+    _file: &Path,
+    _home: &Path,
+    _dirs: &[(&str, Option<&Path>)],
+) -> AreiaResult<()> {
+    Err(AreiaError::WritingUserDirsNotSupported(
+        "No `user-dirs.dirs` on macOS".to_string(),
+    ))
 }
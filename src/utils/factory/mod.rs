@@ -13,46 +13,115 @@ mod linux;
 #[cfg(target_os = "linux")]
 use linux as os;
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
-use crate::error::AreiaResult;
+use crate::{DirKind, DirSource, EnvSource, error::AreiaResult};
 
-pub fn cache_dir(home: PathBuf) -> PathBuf {
-    os::cache_dir(home)
+/// The user directories resolved by the OS
+pub struct UsrDirs {
+    /// Every user directory kind, with the source it was resolved from
+    pub dirs: HashMap<DirKind, (Option<PathBuf>, DirSource)>,
+    /// Additional `XDG_xxx_DIR` entries of `user-dirs.dirs`, keyed without the affixes
+    pub custom: BTreeMap<String, PathBuf>,
 }
 
-pub fn config_dir(home: PathBuf) -> PathBuf {
-    os::config_dir(home)
+pub fn cache_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    os::cache_dir(env, home)
 }
 
-pub fn config_local_dir(home: PathBuf) -> PathBuf {
-    os::config_local_dir(home)
+pub fn config_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    os::config_dir(env, home)
 }
 
-pub fn data_dir(home: PathBuf) -> PathBuf {
-    os::data_dir(home)
+pub fn config_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    os::config_local_dir(env, home)
 }
 
-pub fn data_local_dir(home: PathBuf) -> PathBuf {
-    os::data_local_dir(home)
+pub fn data_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    os::data_dir(env, home)
 }
 
-pub fn preference_dir(home: PathBuf) -> PathBuf {
-    os::preference_dir(home)
+pub fn data_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    os::data_local_dir(env, home)
 }
 
-pub fn runtime_dir() -> Option<PathBuf> {
-    os::runtime_dir()
+pub fn preference_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    os::preference_dir(env, home)
 }
 
-pub fn state_dir(home: PathBuf) -> Option<PathBuf> {
-    os::state_dir(home)
+pub fn runtime_dir(env: &dyn EnvSource) -> Option<PathBuf> {
+    os::runtime_dir(env)
 }
 
-pub fn executable_dir(home: PathBuf) -> Option<PathBuf> {
-    os::executable_dir(home)
+pub fn state_dir(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    os::state_dir(env, home)
 }
 
-pub fn get_usr_dirs(home: PathBuf) -> AreiaResult<HashMap<String, Option<PathBuf>>> {
-    os::get_usr_dirs(home)
+pub fn executable_dir(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    os::executable_dir(env, home)
+}
+
+pub fn data_dirs(env: &dyn EnvSource) -> Vec<PathBuf> {
+    os::data_dirs(env)
+}
+
+pub fn config_dirs(env: &dyn EnvSource) -> Vec<PathBuf> {
+    os::config_dirs(env)
+}
+
+pub fn system_root_dir() -> AreiaResult<PathBuf> {
+    os::system_root_dir()
+}
+
+pub fn system_bin_dir() -> Option<PathBuf> {
+    os::system_bin_dir()
+}
+
+pub fn system_local_bin_dir() -> Option<PathBuf> {
+    os::system_local_bin_dir()
+}
+
+pub fn system_data_dir() -> Option<PathBuf> {
+    os::system_data_dir()
+}
+
+pub fn system_share_dir() -> Option<PathBuf> {
+    os::system_share_dir()
+}
+
+pub fn system_config_dir() -> Option<PathBuf> {
+    os::system_config_dir()
+}
+
+pub fn project_path(qualifier: &str, organization: &str, application: &str) -> PathBuf {
+    os::project_path(qualifier, organization, application)
+}
+
+/// The subdirectory of the project path a project directory of `kind` lives in, if several
+/// kinds share a base directory on this OS
+pub fn project_subdir(kind: DirKind) -> Option<&'static str> {
+    os::project_subdir(kind)
+}
+
+pub fn base_dir_source(
+    env: &dyn EnvSource,
+    kind: DirKind,
+) -> (DirSource, Option<(String, OsString)>) {
+    os::base_dir_source(env, kind)
+}
+
+pub fn get_usr_dirs(env: &dyn EnvSource, home: PathBuf) -> AreiaResult<UsrDirs> {
+    os::get_usr_dirs(env, home)
+}
+
+pub fn user_dirs_file(env: &dyn EnvSource, home: PathBuf) -> Option<PathBuf> {
+    os::user_dirs_file(env, home)
+}
+
+pub fn write_usr_dirs(file: &Path, home: &Path, dirs: &[(&str, Option<&Path>)]) -> AreiaResult<()> {
+    os::write_usr_dirs(file, home, dirs)
 }
@@ -1,69 +1,170 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
+use super::UsrDirs;
 use crate::{
-    error::AreiaResult,
+    DirKind, DirSource, EnvSource,
+    error::{AreiaError, AreiaResult},
     utils::ffi::windows::{FolderID, get_path},
 };
 
 // TODO: If `get_path` returns an error, it is currently suppressed and an empty path is returned - I should fix that
 
-pub fn cache_dir(home: PathBuf) -> PathBuf {
-    data_local_dir(home)
+pub fn cache_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    data_local_dir(env, home)
 }
 
-pub fn config_dir(home: PathBuf) -> PathBuf {
-    data_dir(home)
+pub fn config_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    data_dir(env, home)
 }
 
-pub fn data_dir(_home: PathBuf) -> PathBuf {
+pub fn data_dir(_env: &dyn EnvSource, _home: PathBuf) -> PathBuf {
     match get_path(FolderID::RoamingAppData) {
         Ok(path) => path,
         Err(_) => PathBuf::new(),
     }
 }
 
-pub fn data_local_dir(_home: PathBuf) -> PathBuf {
+pub fn data_local_dir(_env: &dyn EnvSource, _home: PathBuf) -> PathBuf {
     match get_path(FolderID::LocalAppData) {
         Ok(path) => path,
         Err(_) => PathBuf::new(),
     }
 }
 
-pub fn runtime_dir() -> Option<PathBuf> {
+pub fn runtime_dir(_env: &dyn EnvSource) -> Option<PathBuf> {
     None
 }
 
-pub fn state_dir(_home: PathBuf) -> Option<PathBuf> {
+pub fn state_dir(_env: &dyn EnvSource, _home: PathBuf) -> Option<PathBuf> {
     None
 }
 
-pub fn executable_dir(_home: PathBuf) -> Option<PathBuf> {
+pub fn executable_dir(_env: &dyn EnvSource, _home: PathBuf) -> Option<PathBuf> {
     None
 }
 
-pub fn config_local_dir(home: PathBuf) -> PathBuf {
-    data_local_dir(home)
+pub fn config_local_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    data_local_dir(env, home)
+}
+
+pub fn preference_dir(env: &dyn EnvSource, home: PathBuf) -> PathBuf {
+    data_dir(env, home)
+}
+
+pub fn data_dirs(_env: &dyn EnvSource) -> Vec<PathBuf> {
+    get_path(FolderID::ProgramData).into_iter().collect()
 }
 
-pub fn preference_dir(home: PathBuf) -> PathBuf {
-    data_dir(home)
+pub fn config_dirs(env: &dyn EnvSource) -> Vec<PathBuf> {
+    data_dirs(env)
 }
 
 fn font_dir(_home: PathBuf) -> Option<PathBuf> {
     None
 }
 
-pub fn get_usr_dirs<P: Into<PathBuf>>(home: P) -> AreiaResult<HashMap<String, Option<PathBuf>>> {
+/// The system drive is always the parent of `{FolderID::ProgramData}`
+pub fn system_root_dir() -> AreiaResult<PathBuf> {
+    let program_data = get_path(FolderID::ProgramData)?;
+    match program_data.parent() {
+        Some(root) => Ok(root.to_path_buf()),
+        None => Err(AreiaError::WindowsError(format!(
+            "System drive: {} has no parent",
+            program_data.display()
+        ))),
+    }
+}
+
+pub fn system_bin_dir() -> Option<PathBuf> {
+    None
+}
+
+pub fn system_local_bin_dir() -> Option<PathBuf> {
+    get_path(FolderID::ProgramFilesX86).ok()
+}
+
+pub fn system_data_dir() -> Option<PathBuf> {
+    get_path(FolderID::ProgramData).ok()
+}
+
+pub fn system_share_dir() -> Option<PathBuf> {
+    None
+}
+
+pub fn system_config_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Windows applications are nested inside their organization, e.g. `Org\App`
+pub fn project_path(_qualifier: &str, organization: &str, application: &str) -> PathBuf {
+    let mut out = PathBuf::new();
+    if !organization.is_empty() {
+        out.push(organization);
+    }
+    out.push(application);
+    out
+}
+
+/// `LocalAppData` holds the cache and the local config and data, `RoamingAppData` the config
+/// and data, so each kind gets a subdirectory, like the `directories` crate does
+pub fn project_subdir(kind: DirKind) -> Option<&'static str> {
+    match kind {
+        DirKind::Cache => Some("cache"),
+        DirKind::Config | DirKind::ConfigLocal | DirKind::Preference => Some("config"),
+        DirKind::Data | DirKind::DataLocal => Some("data"),
+        _ => None,
+    }
+}
+
+/// The base directories are fixed, no environment variable is read
+pub fn base_dir_source(
+    _env: &dyn EnvSource,
+    _kind: DirKind,
+) -> (DirSource, Option<(String, OsString)>) {
+    (DirSource::Default, None)
+}
+
+pub fn get_usr_dirs<P: Into<PathBuf>>(_env: &dyn EnvSource, home: P) -> AreiaResult<UsrDirs> {
     let mut out = HashMap::new();
 
-    out.insert("MUSIC".to_string(), Some(get_path(FolderID::Music)?));
-    out.insert("DESKTOP".to_string(), Some(get_path(FolderID::Desktop)?));
-    out.insert("DOCUMENTS".to_string(), Some(get_path(FolderID::Document)?));
-    out.insert("PICTURES".to_string(), Some(get_path(FolderID::Picture)?));
-    out.insert("PUBLICSHARE".to_string(), Some(get_path(FolderID::Public)?));
-    out.insert("TEMPLATES".to_string(), Some(get_path(FolderID::Template)?));
-    out.insert("VIDEOS".to_string(), Some(get_path(FolderID::Video)?));
-    out.insert("FONTS".to_string(), font_dir(home.into()));
-    out.insert("DOWNLOADS".to_string(), Some(get_path(FolderID::Download)?));
-    Ok(out)
+    out.insert(DirKind::Audio, Some(get_path(FolderID::Music)?));
+    out.insert(DirKind::Desktop, Some(get_path(FolderID::Desktop)?));
+    out.insert(DirKind::Document, Some(get_path(FolderID::Document)?));
+    out.insert(DirKind::Picture, Some(get_path(FolderID::Picture)?));
+    out.insert(DirKind::Public, Some(get_path(FolderID::Public)?));
+    out.insert(DirKind::Template, Some(get_path(FolderID::Template)?));
+    out.insert(DirKind::Video, Some(get_path(FolderID::Video)?));
+    let home = home.into();
+    out.insert(DirKind::Font, font_dir(home.clone()));
+    out.insert(DirKind::Download, Some(get_path(FolderID::Download)?));
+    // Screenshots is only created by the Snipping Tool, so it may not exist yet
+    out.insert(DirKind::Screenshot, get_path(FolderID::Screenshot).ok());
+    // The default location of Visual Studio for new projects
+    out.insert(DirKind::Project, Some(home.join("source").join("repos")));
+    Ok(UsrDirs {
+        dirs: out
+            .into_iter()
+            .map(|(kind, val)| (kind, (val, DirSource::Default)))
+            .collect(),
+        custom: BTreeMap::new(),
+    })
+}
+
+/// Windows has no `user-dirs.dirs` file, the user directories are fixed
+pub fn user_dirs_file(_env: &dyn EnvSource, _home: PathBuf) -> Option<PathBuf> {
+    None
+}
+
+pub fn write_usr_dirs(
+    _file: &Path,
+    _home: &Path,
+    _dirs: &[(&str, Option<&Path>)],
+) -> AreiaResult<()> {
+    Err(AreiaError::WritingUserDirsNotSupported(
+        "No `user-dirs.dirs` on Windows".to_string(),
+    ))
 }
@@ -0,0 +1,193 @@
+use std::ffi::{CString, OsString, c_char};
+use std::io::{Error, ErrorKind, Result};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::Path;
+
+pub const IN_CLOSE_WRITE: u32 = 0x0000_0008;
+pub const IN_MOVED_FROM: u32 = 0x0000_0040;
+pub const IN_MOVED_TO: u32 = 0x0000_0080;
+pub const IN_CREATE: u32 = 0x0000_0100;
+pub const IN_DELETE: u32 = 0x0000_0200;
+pub const IN_DELETE_SELF: u32 = 0x0000_0400;
+pub const IN_MOVE_SELF: u32 = 0x0000_0800;
+pub const IN_Q_OVERFLOW: u32 = 0x0000_4000;
+pub const IN_IGNORED: u32 = 0x0000_8000;
+pub const IN_ONLYDIR: u32 = 0x0100_0000;
+
+/// `O_NONBLOCK` and `O_CLOEXEC`, shared by `inotify_init1` and `pipe2`
+const NONBLOCK: i32 = 0o4000;
+const CLOEXEC: i32 = 0o2_000_000;
+const POLLIN: i16 = 0x1;
+const EINTR: i32 = 4;
+const EAGAIN: i32 = 11;
+
+/// Size of `struct inotify_event` without the trailing name
+const EVENT_HEADER: usize = 16;
+
+#[repr(C)]
+struct PollFd {
+    fd: i32,
+    events: i16,
+    revents: i16,
+}
+
+#[link(name = "c")]
+unsafe extern "C" {
+    fn inotify_init1(flags: i32) -> i32;
+    fn inotify_add_watch(fd: i32, path: *const c_char, mask: u32) -> i32;
+    fn inotify_rm_watch(fd: i32, wd: i32) -> i32;
+    fn pipe2(fds: *mut i32, flags: i32) -> i32;
+    fn poll(fds: *mut PollFd, nfds: std::ffi::c_ulong, timeout: i32) -> i32;
+    fn read(fd: i32, buf: *mut u8, count: usize) -> isize;
+    fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+    fn close(fd: i32) -> i32;
+}
+
+/// A single event read from an inotify instance
+pub struct InotifyEvent {
+    pub wd: i32,
+    pub mask: u32,
+    /// The name of the entry inside the watched directory, `None` for the directory itself
+    pub name: Option<OsString>,
+}
+
+/// An inotify instance, closed on drop
+pub struct Inotify {
+    fd: i32,
+}
+
+impl Inotify {
+    pub fn new() -> Result<Inotify> {
+        let fd = unsafe { inotify_init1(NONBLOCK | CLOEXEC) };
+        if fd < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(Inotify { fd })
+    }
+
+    /// Watches `path` for the events in `mask`, returns the watch descriptor
+    ///
+    /// Watching the same inode again replaces the mask and returns the same descriptor.
+    pub fn add_watch(&self, path: &Path, mask: u32) -> Result<i32> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        let wd = unsafe { inotify_add_watch(self.fd, path.as_ptr(), mask) };
+        if wd < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(wd)
+    }
+
+    pub fn rm_watch(&self, wd: i32) -> Result<()> {
+        if unsafe { inotify_rm_watch(self.fd, wd) } < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Blocks until events are available or `wake` was woken
+    ///
+    /// Returns `false` if `wake` was woken.
+    pub fn wait(&self, wake: &WakePipe) -> Result<bool> {
+        let mut fds = [
+            PollFd {
+                fd: self.fd,
+                events: POLLIN,
+                revents: 0,
+            },
+            PollFd {
+                fd: wake.read,
+                events: POLLIN,
+                revents: 0,
+            },
+        ];
+        loop {
+            let ret = unsafe { poll(fds.as_mut_ptr(), 2, -1) };
+            if ret >= 0 {
+                return Ok(fds[1].revents == 0);
+            }
+            let err = Error::last_os_error();
+            if err.raw_os_error() != Some(EINTR) {
+                return Err(err);
+            }
+        }
+    }
+
+    /// Reads all pending events without blocking
+    pub fn read_events(&self) -> Result<Vec<InotifyEvent>> {
+        // Large enough for at least one event with a name of `NAME_MAX`
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut events = Vec::new();
+        loop {
+            let len = unsafe { read(self.fd, buf.as_mut_ptr(), buf.len()) };
+            if len < 0 {
+                let err = Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(EAGAIN) => Ok(events),
+                    Some(EINTR) => continue,
+                    _ => Err(err),
+                };
+            }
+            let len = usize::try_from(len).map_err(|_| Error::from(ErrorKind::InvalidData))?;
+            let mut offset = 0;
+            while offset + EVENT_HEADER <= len {
+                let field = |at: usize| {
+                    let bytes = buf[offset + at..offset + at + 4]
+                        .try_into()
+                        .expect("4 bytes");
+                    u32::from_ne_bytes(bytes)
+                };
+                let wd = i32::from_ne_bytes(buf[offset..offset + 4].try_into().expect("4 bytes"));
+                let mask = field(4);
+                let name_len = field(12) as usize;
+                let name_start = offset + EVENT_HEADER;
+                // The name is padded with nul bytes
+                let name = buf[name_start..name_start + name_len]
+                    .split(|byte| *byte == 0)
+                    .next()
+                    .filter(|name| !name.is_empty())
+                    .map(|name| OsString::from_vec(name.to_vec()));
+                events.push(InotifyEvent { wd, mask, name });
+                offset = name_start + name_len;
+            }
+        }
+    }
+}
+
+impl Drop for Inotify {
+    fn drop(&mut self) {
+        unsafe { close(self.fd) };
+    }
+}
+
+/// A pipe used to wake a thread blocked in `Inotify::wait()`, closed on drop
+pub struct WakePipe {
+    read: i32,
+    write: i32,
+}
+
+impl WakePipe {
+    pub fn new() -> Result<WakePipe> {
+        let mut fds = [0i32; 2];
+        if unsafe { pipe2(fds.as_mut_ptr(), NONBLOCK | CLOEXEC) } < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(WakePipe {
+            read: fds[0],
+            write: fds[1],
+        })
+    }
+
+    pub fn wake(&self) {
+        // A full pipe already wakes the reader, so the result does not matter
+        unsafe { write(self.write, [1u8].as_ptr(), 1) };
+    }
+}
+
+impl Drop for WakePipe {
+    fn drop(&mut self) {
+        unsafe {
+            close(self.read);
+            close(self.write);
+        }
+    }
+}
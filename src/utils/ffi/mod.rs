@@ -1,3 +1,6 @@
+#[cfg(target_os = "linux")]
+pub mod linux;
+
 #[cfg(any(target_os = "macos", all(target_os = "macos", doc)))]
 pub mod macos;
 
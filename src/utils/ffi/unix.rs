@@ -1,46 +1,216 @@
-use std::ffi::{CStr, OsString};
-use std::os::unix::ffi::OsStringExt;
-use std::path::PathBuf;
+use std::ffi::{CStr, CString, OsString, c_char};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
 
+#[cfg(not(target_os = "macos"))]
 #[repr(C)]
 struct Passwd {
-    pub pw_name: *mut i8,
-    pub pw_passwd: *mut i8,
+    pub pw_name: *mut c_char,
+    pub pw_passwd: *mut c_char,
     pub pw_uid: u32,
     pub pw_gid: u32,
-    pub pw_gecos: *mut i8,
-    pub pw_dir: *mut i8,
-    pub pw_shell: *mut i8,
+    pub pw_gecos: *mut c_char,
+    pub pw_dir: *mut c_char,
+    pub pw_shell: *mut c_char,
 }
 
+/// The BSD layout, with the password change time and class before `pw_gecos` and the account
+/// expiration after `pw_shell`
+#[cfg(target_os = "macos")]
+#[repr(C)]
+struct Passwd {
+    pub pw_name: *mut c_char,
+    pub pw_passwd: *mut c_char,
+    pub pw_uid: u32,
+    pub pw_gid: u32,
+    pub pw_change: std::ffi::c_long,
+    pub pw_class: *mut c_char,
+    pub pw_gecos: *mut c_char,
+    pub pw_dir: *mut c_char,
+    pub pw_shell: *mut c_char,
+    pub pw_expire: std::ffi::c_long,
+}
+
+/// `O_NOFOLLOW` for `OpenOptionsExt::custom_flags()`
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "aarch64",
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64"
+    )
+))]
+pub const O_NOFOLLOW: i32 = 0o100_000;
+#[cfg(all(
+    target_os = "linux",
+    not(any(
+        target_arch = "aarch64",
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64"
+    ))
+))]
+pub const O_NOFOLLOW: i32 = 0o400_000;
+#[cfg(not(target_os = "linux"))]
+pub const O_NOFOLLOW: i32 = 0x0100;
+
 #[link(name = "c")]
 unsafe extern "C" {
     fn getuid() -> u32;
+    fn geteuid() -> u32;
+    fn sysconf(name: i32) -> std::ffi::c_long;
+    fn lchown(path: *const c_char, owner: u32, group: u32) -> i32;
+    fn getpwnam_r(
+        name: *const c_char,
+        pwd: *mut Passwd,
+        buf: *mut c_char,
+        buflen: usize,
+        result: *mut *mut Passwd,
+    ) -> i32;
     fn getpwuid_r(
         uid: u32,
         pwd: *mut Passwd,
-        buf: *mut i8,
+        buf: *mut c_char,
         buflen: usize,
         result: *mut *mut Passwd,
     ) -> i32;
+    #[cfg(target_os = "linux")]
+    fn statfs(path: *const c_char, buf: *mut std::ffi::c_long) -> i32;
+}
+
+/// The uid of the current user
+pub fn current_uid() -> u32 {
+    unsafe { getuid() }
+}
+
+/// Whether the process runs with the privileges of root
+pub fn is_root() -> bool {
+    unsafe { geteuid() == 0 }
+}
+
+/// The magic number of the filesystem `path` is on, `None` if `statfs` fails
+#[cfg(target_os = "linux")]
+pub fn filesystem_type(path: &Path) -> Option<u32> {
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // `struct statfs` differs between architectures, but always starts with `f_type` - so an
+    //      oversized buffer is used instead of mirroring the struct
+    let mut buf = [0 as std::ffi::c_long; 64];
+    let ret = unsafe { statfs(path.as_ptr(), buf.as_mut_ptr()) };
+    if ret == 0 {
+        // The magic numbers are 32 bit, but `f_type` is sign extended where `c_long` is `i32`,
+        //      e.g. CIFS would not match - so only the low 32 bits are compared
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(buf[0] as u32)
+    } else {
+        None
+    }
 }
 
 pub fn get_unix_home_fallback() -> Option<PathBuf> {
-    unsafe {
-        let uid = getuid();
-        let mut pwd = std::mem::zeroed::<Passwd>();
-        let mut res = std::ptr::null_mut();
-        // Some systems define sysconf(_SC_GETPW_R_SIZE_MAX) to something different - too bad!
-        let mut buf = vec![0i8; 1024];
+    get_unix_home_by_uid(current_uid())
+}
 
-        let ret = getpwuid_r(uid, &mut pwd, buf.as_mut_ptr(), buf.len(), &mut res);
+/// `_SC_GETPW_R_SIZE_MAX`, see `sysconf(3)`
+#[cfg(target_os = "linux")]
+const SC_GETPW_R_SIZE_MAX: i32 = 70;
+#[cfg(not(target_os = "linux"))]
+const SC_GETPW_R_SIZE_MAX: i32 = 71;
 
-        if ret == 0 && !res.is_null() {
-            Some(PathBuf::from(OsString::from_vec(
-                CStr::from_ptr(pwd.pw_dir).to_bytes().to_vec(),
-            )))
-        } else {
-            None
+/// `errno` of `getpw*_r` if the buffer is too small
+const ERANGE: i32 = 34;
+
+/// Errors `getpw*_r` may return instead of an empty result for unknown users, see
+/// `getpwnam_r(3)`: `ENOENT`, `ESRCH`, `EBADF` and `EPERM`
+const NOT_FOUND_ERRORS: [i32; 4] = [2, 3, 9, 1];
+
+/// Entries larger than this are treated as an error instead of growing the buffer forever
+const MAX_PASSWD_BUFFER: usize = 1 << 20;
+
+/// A passwd entry with every field except the password
+pub struct PasswdEntry {
+    pub name: OsString,
+    pub uid: u32,
+    pub gid: u32,
+    pub gecos: OsString,
+    pub home: PathBuf,
+    pub shell: PathBuf,
+}
+
+/// Copies a string field out of a filled `Passwd`, some systems leave unused fields null
+unsafe fn passwd_field(field: *const c_char) -> OsString {
+    if field.is_null() {
+        OsString::new()
+    } else {
+        OsString::from_vec(unsafe { CStr::from_ptr(field) }.to_bytes().to_vec())
+    }
+}
+
+/// Calls `lookup` (`getpwuid_r` or `getpwnam_r`) with a growing buffer
+///
+/// Starts with `sysconf(_SC_GETPW_R_SIZE_MAX)` - which is only a hint - and doubles the buffer
+/// on `ERANGE`, as entries from LDAP or SSSD can exceed it.
+/// Returns `Ok(None)` if the user does not exist.
+fn lookup_passwd(
+    lookup: impl Fn(*mut Passwd, *mut c_char, usize, *mut *mut Passwd) -> i32,
+) -> std::io::Result<Option<PasswdEntry>> {
+    let hint = unsafe { sysconf(SC_GETPW_R_SIZE_MAX) };
+    let mut len = usize::try_from(hint)
+        .unwrap_or(0)
+        .clamp(1024, MAX_PASSWD_BUFFER);
+    loop {
+        let mut pwd = unsafe { std::mem::zeroed::<Passwd>() };
+        let mut res = std::ptr::null_mut();
+        let mut buf = vec![0 as c_char; len];
+
+        let ret = lookup(&raw mut pwd, buf.as_mut_ptr(), buf.len(), &raw mut res);
+        match ret {
+            0 if res.is_null() => return Ok(None),
+            0 => unsafe {
+                return Ok(Some(PasswdEntry {
+                    name: passwd_field(pwd.pw_name),
+                    uid: pwd.pw_uid,
+                    gid: pwd.pw_gid,
+                    gecos: passwd_field(pwd.pw_gecos),
+                    home: PathBuf::from(passwd_field(pwd.pw_dir)),
+                    shell: PathBuf::from(passwd_field(pwd.pw_shell)),
+                }));
+            },
+            ERANGE if len < MAX_PASSWD_BUFFER => len *= 2,
+            err if NOT_FOUND_ERRORS.contains(&err) => return Ok(None),
+            err => return Err(std::io::Error::from_raw_os_error(err)),
         }
     }
 }
+
+/// Looks up the user with the uid `uid` in the passwd database
+pub fn get_passwd_entry_by_uid(uid: u32) -> std::io::Result<Option<PasswdEntry>> {
+    lookup_passwd(|pwd, buf, len, res| unsafe { getpwuid_r(uid, pwd, buf, len, res) })
+}
+
+/// Looks up the user called `name` in the passwd database
+pub fn get_passwd_entry_by_name(name: &str) -> std::io::Result<Option<PasswdEntry>> {
+    // A name with a nul byte can't be in the database
+    let Ok(name) = CString::new(name) else {
+        return Ok(None);
+    };
+    lookup_passwd(|pwd, buf, len, res| unsafe { getpwnam_r(name.as_ptr(), pwd, buf, len, res) })
+}
+
+/// Looks up the home directory of the user with the uid `uid` in the passwd database
+pub fn get_unix_home_by_uid(uid: u32) -> Option<PathBuf> {
+    get_passwd_entry_by_uid(uid)
+        .ok()
+        .flatten()
+        .map(|entry| entry.home)
+}
+
+/// Changes the owner of `path` without following a symlink
+pub fn change_owner(path: &Path, uid: u32, gid: u32) -> std::io::Result<()> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    if unsafe { lchown(path.as_ptr(), uid, gid) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
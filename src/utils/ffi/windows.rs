@@ -19,6 +19,9 @@ pub enum FolderID {
     Public,
     Template,
     Video,
+    ProgramFilesX86,
+    ProgramData,
+    Screenshot,
 }
 
 impl Display for FolderID {
@@ -35,6 +38,9 @@ impl Display for FolderID {
             FolderID::Public => write!(f, "Public"),
             FolderID::Template => write!(f, "Template"),
             FolderID::Video => write!(f, "Video"),
+            FolderID::ProgramFilesX86 => write!(f, "ProgramFilesX86"),
+            FolderID::ProgramData => write!(f, "ProgramData"),
+            FolderID::Screenshot => write!(f, "Screenshot"),
         }
     }
 }
@@ -125,6 +131,28 @@ const FOLDERID_VIDEO: GUID = GUID {
     data4: [0x84, 0x1c, 0xab, 0x7c, 0x74, 0xe4, 0xdd, 0xfc],
 };
 
+// x86 has the same expected output on 64bit machines, if the app is 32 or 64 bit
+const FOLDERID_PROGRAMFILESX86: GUID = GUID {
+    data1: 0x7c5a40ef,
+    data2: 0xa0fb,
+    data3: 0x4bfc,
+    data4: [0x87, 0x4a, 0xc0, 0xf2, 0xe0, 0xb9, 0xfa, 0x8e],
+};
+
+const FOLDERID_PROGRAMDATA: GUID = GUID {
+    data1: 0x62ab5d82,
+    data2: 0xfdc1,
+    data3: 0x4dc3,
+    data4: [0xa9, 0xdd, 0x07, 0x0d, 0x1d, 0x49, 0x5d, 0x97],
+};
+
+const FOLDERID_SCREENSHOTS: GUID = GUID {
+    data1: 0xb7bede81,
+    data2: 0xdf94,
+    data3: 0x4682,
+    data4: [0xa7, 0xd8, 0x57, 0xa5, 0x26, 0x20, 0xb8, 0x6f],
+};
+
 const FILE_ATTRIBUTE_HIDDEN: u32 = 0x02;
 // For "super" hiding like on mac
 const FILE_ATTRIBUTE_SYSTEM: u32 = 0x04;
@@ -273,6 +301,9 @@ pub fn get_path(folder: FolderID) -> AreiaResult<PathBuf> {
         FolderID::Public => &FOLDERID_PUBLIC,
         FolderID::Template => &FOLDERID_TEMPLATE,
         FolderID::Video => &FOLDERID_VIDEO,
+        FolderID::ProgramFilesX86 => &FOLDERID_PROGRAMFILESX86,
+        FolderID::ProgramData => &FOLDERID_PROGRAMDATA,
+        FolderID::Screenshot => &FOLDERID_SCREENSHOTS,
     };
     let result = unsafe { SHGetKnownFolderPath(rfid, 0, std::ptr::null_mut(), &mut path_ptr) };
     if result == 0 && !path_ptr.is_null() {
@@ -0,0 +1,150 @@
+use std::{
+    ffi::OsStr,
+    io::{ErrorKind, Write},
+    os::unix::ffi::OsStrExt,
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::error::{AreiaError, AreiaResult};
+
+/// The file listing the names of hidden entries, read by GNOME Files, Dolphin, Thunar and Nemo
+const HIDDEN_LIST: &str = ".hidden";
+
+/// Distinguishes the temporary files of concurrent rewrites inside this process
+static REWRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// Checks if any component of the path is listed in the `.hidden` file of its parent.
+pub fn is_superhidden(path: &Path) -> AreiaResult<bool> {
+    Ok(!listed_components(path)?.is_empty())
+}
+
+/// Lists the file or directory in the `.hidden` file of its parent, the name stays unchanged.
+pub fn superhide(path: &Path) -> AreiaResult<PathBuf> {
+    let (dir, name) = split_path(path)?;
+    update_hidden_list(dir, |entries| {
+        if !entries
+            .iter()
+            .any(|entry| entry.as_slice() == name.as_bytes())
+        {
+            entries.push(name.as_bytes().to_vec());
+        }
+    })?;
+    Ok(path.to_path_buf())
+}
+
+/// Removes every listed component of the path from the `.hidden` file of its parent, so the
+/// path is visible afterwards.
+pub fn super_unhide(path: &Path) -> AreiaResult<PathBuf> {
+    for component in listed_components(path)? {
+        let (dir, name) = split_path(&component)?;
+        update_hidden_list(dir, |entries| {
+            entries.retain(|entry| entry.as_slice() != name.as_bytes());
+        })?;
+    }
+    Ok(path.to_path_buf())
+}
+
+/// The prefixes of `path` whose last component is listed in the `.hidden` file of its parent
+fn listed_components(path: &Path) -> AreiaResult<Vec<PathBuf>> {
+    let mut listed = Vec::new();
+    let mut prefix = PathBuf::new();
+    for component in path.components() {
+        let parent = list_dir(&prefix).to_path_buf();
+        prefix.push(component);
+        if let Component::Normal(name) = component
+            && read_hidden_list(&parent)?
+                .iter()
+                .any(|entry| entry.as_slice() == name.as_bytes())
+        {
+            listed.push(prefix.clone());
+        }
+    }
+    Ok(listed)
+}
+
+/// The directory holding the `.hidden` file and the name listed in it
+fn split_path(path: &Path) -> AreiaResult<(&Path, &OsStr)> {
+    match path.components().next_back() {
+        Some(Component::Normal(name)) => {
+            Ok((list_dir(path.parent().unwrap_or(Path::new(""))), name))
+        }
+        _ => Err(AreiaError::SuperHidingNotSupported(format!(
+            "{} does not end in a file or directory name",
+            path.display()
+        ))),
+    }
+}
+
+/// The parent of a relative path with a single component is empty, the list is in `.` then
+fn list_dir(parent: &Path) -> &Path {
+    if parent.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        parent
+    }
+}
+
+/// The names listed in the `.hidden` file of `dir`, empty if there is none
+fn read_hidden_list(dir: &Path) -> AreiaResult<Vec<Vec<u8>>> {
+    let content = match std::fs::read(dir.join(HIDDEN_LIST)) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(content
+        .split(|byte| *byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .filter(|line| !line.is_empty())
+        .map(<[u8]>::to_vec)
+        .collect())
+}
+
+/// Applies `update` to the deduplicated `.hidden` file of `dir`
+///
+/// The file is only written if it changed, it is replaced atomically so file managers never see
+/// a partial list, keeping its mode. An empty list removes the file.
+fn update_hidden_list(dir: &Path, update: impl FnOnce(&mut Vec<Vec<u8>>)) -> AreiaResult<()> {
+    let current = read_hidden_list(dir)?;
+    let mut entries: Vec<Vec<u8>> = Vec::with_capacity(current.len());
+    for entry in &current {
+        if !entries.contains(entry) {
+            entries.push(entry.clone());
+        }
+    }
+    update(&mut entries);
+    if entries == current {
+        return Ok(());
+    }
+
+    let list = dir.join(HIDDEN_LIST);
+    if entries.is_empty() {
+        return match std::fs::remove_file(&list) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        };
+    }
+
+    // The replacement keeps the mode of the current list, e.g. one shared with a group
+    let permissions = std::fs::metadata(&list).ok().map(|meta| meta.permissions());
+    let tmp = dir.join(format!(
+        "{HIDDEN_LIST}.{}.{}.tmp",
+        std::process::id(),
+        REWRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let written = std::fs::File::create(&tmp).and_then(|mut file| {
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        for entry in &entries {
+            file.write_all(entry)?;
+            file.write_all(b"\n")?;
+        }
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|()| std::fs::rename(&tmp, &list)) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(err.into());
+    }
+    Ok(())
+}
@@ -1,15 +1,24 @@
 mod ffi;
-use crate::error::{AreiaError, AreiaResult};
-use std::path::PathBuf;
+use crate::{
+    DirSource, RuntimeDirViolation, User, UserInfo,
+    error::{AreiaError, AreiaResult},
+};
+use std::path::{Path, PathBuf};
 
 pub mod factory;
 
+#[cfg(target_os = "linux")]
+pub use ffi::linux as inotify;
+
 #[cfg(any(unix, doc))]
 mod unix;
 
 #[cfg(unix)]
 use unix as os;
 
+#[cfg(target_os = "linux")]
+mod linux;
+
 #[cfg(any(target_os = "macos", all(target_os = "macos", doc)))]
 mod macos;
 
@@ -22,6 +31,30 @@ pub fn get_home() -> AreiaResult<PathBuf> {
     os::get_home()
 }
 
+pub fn get_home_source() -> DirSource {
+    os::get_home_source()
+}
+
+pub fn get_user_home(user: &User) -> AreiaResult<PathBuf> {
+    os::get_user_home(user)
+}
+
+pub fn get_user_info(user: &User) -> AreiaResult<UserInfo> {
+    os::get_user_info(user)
+}
+
+pub fn get_current_user_info() -> AreiaResult<UserInfo> {
+    os::get_current_user_info()
+}
+
+pub fn is_privileged() -> bool {
+    os::is_privileged()
+}
+
+pub fn change_owner_to(path: &Path, user: &User) -> AreiaResult<()> {
+    os::change_owner_to(path, user)
+}
+
 pub fn hide_path(path: &mut PathBuf) -> AreiaResult<PathBuf> {
     os::hide_file(path)
 }
@@ -36,8 +69,11 @@ pub fn is_hidden(path: &PathBuf) -> AreiaResult<bool> {
 
 // Allows because I want cargo check to shut up
 #[allow(unused_variables, unreachable_code)]
-/// Only supports Mac & Windows
+/// Only supports Linux, Mac & Windows
 pub fn is_superhidden(path: &PathBuf) -> AreiaResult<bool> {
+    #[cfg(target_os = "linux")]
+    return linux::is_superhidden(path);
+
     #[cfg(target_os = "macos")]
     return macos::is_superhidden(path);
 
@@ -50,8 +86,11 @@ pub fn is_superhidden(path: &PathBuf) -> AreiaResult<bool> {
 }
 
 #[allow(unused_variables, unreachable_code)]
-/// Only supports Mac & Windows
+/// Only supports Linux, Mac & Windows
 pub fn super_hide(path: &mut PathBuf) -> AreiaResult<PathBuf> {
+    #[cfg(target_os = "linux")]
+    return linux::superhide(path);
+
     #[cfg(target_os = "macos")]
     return macos::superhide(path);
 
@@ -64,8 +103,11 @@ pub fn super_hide(path: &mut PathBuf) -> AreiaResult<PathBuf> {
 }
 
 #[allow(unused_variables, unreachable_code)]
-/// Only supports Mac & Windows
+/// Only supports Linux, Mac & Windows
 pub fn super_unhide(path: &mut PathBuf) -> AreiaResult<PathBuf> {
+    #[cfg(target_os = "linux")]
+    return linux::super_unhide(path);
+
     #[cfg(target_os = "macos")]
     return macos::super_unhide(path);
 
@@ -77,6 +119,35 @@ pub fn super_unhide(path: &mut PathBuf) -> AreiaResult<PathBuf> {
     ))
 }
 
+/// Like `create_all_dir_with_file()`, but every created directory and the file are handed over
+/// to `user`. Meant for running as root, so nothing `user` planted is followed or overwritten:
+/// already existing parents keep their owner, an existing file is left alone and a symlink in
+/// place of the file is an error.
+pub fn create_all_dir_with_file_for(path: &Path, user: &User) -> AreiaResult<()> {
+    // Fails for unknown users and on Windows before anything is created
+    get_user_info(user)?;
+    let mut missing = Vec::new();
+    let mut ancestor = path.parent();
+    while let Some(dir) = ancestor {
+        if dir.as_os_str().is_empty() || dir.exists() {
+            break;
+        }
+        missing.push(dir);
+        ancestor = dir.parent();
+    }
+    for dir in missing.into_iter().rev() {
+        match std::fs::create_dir(dir) {
+            Ok(()) => change_owner_to(dir, user)?,
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    if os::create_new_file(path)? {
+        change_owner_to(path, user)?;
+    }
+    Ok(())
+}
+
 pub fn create_all_dir_with_file(path: &PathBuf) -> AreiaResult<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
@@ -85,6 +156,22 @@ pub fn create_all_dir_with_file(path: &PathBuf) -> AreiaResult<()> {
     Ok(())
 }
 
+/// Checks the requirements of the XDG spec for the runtime directory, returns every violation
+pub fn runtime_dir_violations(path: &Path) -> Vec<RuntimeDirViolation> {
+    os::runtime_dir_violations(path)
+}
+
+/// Creates a private per-user runtime directory in the temporary directory
+pub fn create_runtime_fallback() -> AreiaResult<PathBuf> {
+    os::create_runtime_fallback()
+}
+
+/// Creates the directory and all missing parents, readable only by the current user where the
+/// OS supports it
+pub fn create_private_dir_all(path: &Path) -> AreiaResult<()> {
+    os::create_private_dir_all(path)
+}
+
 pub fn delete_all_dir_with_files(path: &PathBuf) -> AreiaResult<()> {
     if path.is_file() {
         if let Some(parent) = path.parent() {
@@ -99,7 +186,7 @@ pub fn delete_all_dir_with_files(path: &PathBuf) -> AreiaResult<()> {
 }
 
 #[cfg(unix)]
-pub fn make_hidden_path(path: &PathBuf) -> PathBuf {
+pub fn make_hidden_path(path: &PathBuf) -> AreiaResult<PathBuf> {
     os::make_hidden_path(path)
 }
 
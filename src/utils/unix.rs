@@ -1,14 +1,29 @@
 use std::{
     ffi::{OsStr, OsString},
-    path::PathBuf,
+    fs::{DirBuilder, Permissions},
+    io::ErrorKind,
+    os::unix::{
+        ffi::{OsStrExt, OsStringExt},
+        fs::{DirBuilderExt, MetadataExt, OpenOptionsExt, PermissionsExt},
+    },
+    path::{Component, Path, PathBuf},
 };
 
 use crate::{
-    BaseDirs, Hidden,
+    DirSource, Hidden, RuntimeDirViolation, User, UserInfo, base_dirs,
     error::{AreiaError, AreiaResult},
+    is_hidden_name,
 };
 
-use super::{create_all_dir_with_file, ffi::unix::get_unix_home_fallback};
+#[cfg(target_os = "linux")]
+use super::ffi::unix::filesystem_type;
+use super::{
+    create_all_dir_with_file,
+    ffi::unix::{
+        O_NOFOLLOW, PasswdEntry, change_owner, current_uid, get_passwd_entry_by_name,
+        get_passwd_entry_by_uid, get_unix_home_fallback, is_root,
+    },
+};
 
 #[cfg(target_os = "macos")]
 use super::ffi::macos::get_mac_home_fallback;
@@ -38,6 +53,76 @@ pub fn get_home() -> AreiaResult<std::path::PathBuf> {
     }
 }
 
+/// Get where the home directory of `get_home()` comes from
+pub fn get_home_source() -> DirSource {
+    if std::env::var_os("HOME").is_some() {
+        DirSource::EnvVar("HOME".to_owned())
+    } else if get_unix_home_fallback().is_some() {
+        DirSource::Passwd
+    } else {
+        DirSource::Default
+    }
+}
+
+fn get_passwd_entry(user: &User) -> AreiaResult<PasswdEntry> {
+    match user {
+        User::Name(name) => get_passwd_entry_by_name(name),
+        User::Uid(uid) => get_passwd_entry_by_uid(*uid),
+    }?
+    .ok_or_else(|| AreiaError::UserNotFound(user.clone()))
+}
+
+/// Get the passwd entry of `user`
+pub fn get_user_info(user: &User) -> AreiaResult<UserInfo> {
+    let entry = get_passwd_entry(user)?;
+    Ok(UserInfo::new(
+        entry.name.to_string_lossy().into_owned(),
+        entry.uid,
+        entry.gid,
+        entry.gecos.to_string_lossy().into_owned(),
+        entry.home,
+        entry.shell,
+    ))
+}
+
+/// Get the passwd entry of the current user
+pub fn get_current_user_info() -> AreiaResult<UserInfo> {
+    get_user_info(&User::Uid(current_uid()))
+}
+
+/// Get the home directory of `user` from the passwd database
+pub fn get_user_home(user: &User) -> AreiaResult<PathBuf> {
+    Ok(get_passwd_entry(user)?.home)
+}
+
+/// Whether the process runs with the privileges of root
+pub fn is_privileged() -> bool {
+    is_root()
+}
+
+/// Hands `path` over to `user` and their primary group, symlinks are not followed
+pub fn change_owner_to(path: &Path, user: &User) -> AreiaResult<()> {
+    let entry = get_passwd_entry(user)?;
+    change_owner(path, entry.uid, entry.gid)?;
+    Ok(())
+}
+
+/// Creates the file `path` if it does not exist yet, without following a symlink
+///
+/// Returns `false` if the file already existed, it is left untouched then.
+pub fn create_new_file(path: &Path) -> AreiaResult<bool> {
+    let created = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .custom_flags(O_NOFOLLOW)
+        .open(path);
+    match created {
+        Ok(_) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::AlreadyExists && !path.is_symlink() => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
 /// Checks if supplied path is an absolute path
 ///
 /// # Returns
@@ -49,36 +134,124 @@ pub fn is_absolute_path(path: OsString) -> Option<PathBuf> {
     if path.is_absolute() { Some(path) } else { None }
 }
 
-/// only handles unix dotfiles
-fn is_component_hidden(path: &OsStr) -> bool {
-    path.to_str()
-        .expect("Unix path is valid UTF-8 by convention")
-        .starts_with(".")
+/// Creates all missing directories with the mode `0700`, as mandated by the XDG spec
+pub fn create_private_dir_all(path: &Path) -> AreiaResult<()> {
+    DirBuilder::new().recursive(true).mode(0o700).create(path)?;
+    Ok(())
+}
+
+/// Magic numbers of network filesystems, see `statfs(2)`
+#[cfg(target_os = "linux")]
+const NETWORK_FILESYSTEMS: [u32; 8] = [
+    0x6969,      // NFS
+    0x517B,      // SMB
+    0xFF53_4D42, // CIFS
+    0xFE53_4D42, // SMB2
+    0x5346_414F, // AFS
+    0x7375_7245, // CODA
+    0x564C,      // NCP
+    0x00C3_6400, // CEPH
+];
+
+/// Checks the requirements of the XDG spec for the runtime directory: owned by the user, only
+/// accessible by the user and on a local filesystem
+pub fn runtime_dir_violations(path: &Path) -> Vec<RuntimeDirViolation> {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return vec![RuntimeDirViolation::NotADirectory];
+    };
+    if !meta.is_dir() {
+        return vec![RuntimeDirViolation::NotADirectory];
+    }
+    let mut out = Vec::new();
+    if meta.uid() != current_uid() {
+        out.push(RuntimeDirViolation::WrongOwner(meta.uid()));
+    }
+    if meta.mode() & 0o777 != 0o700 {
+        out.push(RuntimeDirViolation::WrongMode(meta.mode() & 0o7777));
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(fs_type) = filesystem_type(path)
+        && NETWORK_FILESYSTEMS.contains(&fs_type)
+    {
+        out.push(RuntimeDirViolation::NotLocal(fs_type));
+    }
+    out
+}
+
+/// Creates the private runtime directory `runtime-{uid}` inside the temporary directory.
+/// An existing directory is only used if it is a real directory owned by the user, its
+/// permissions are reset to `0700`.
+pub fn create_runtime_fallback() -> AreiaResult<PathBuf> {
+    let uid = current_uid();
+    let path = std::env::temp_dir().join(format!("runtime-{uid}"));
+    match DirBuilder::new().mode(0o700).create(&path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            // Never follow a symlink, another user could have planted it
+            let meta = std::fs::symlink_metadata(&path)?;
+            if !meta.is_dir() {
+                return Err(AreiaError::InvalidRuntimeDir(
+                    path,
+                    vec![RuntimeDirViolation::NotADirectory],
+                ));
+            }
+            if meta.uid() == uid && meta.mode() & 0o777 != 0o700 {
+                std::fs::set_permissions(&path, Permissions::from_mode(0o700))?;
+            }
+        }
+        Err(err) => return Err(AreiaError::IoError(err)),
+    }
+
+    let violations = runtime_dir_violations(&path);
+    if violations.is_empty() {
+        Ok(path)
+    } else {
+        Err(AreiaError::InvalidRuntimeDir(path, violations))
+    }
 }
 
+/// only handles unix dotfiles, `.` and `..` are never hidden
+fn is_component_hidden(component: Component) -> bool {
+    matches!(component, Component::Normal(name) if is_hidden_name(name))
+}
+
+/// The components of the lexically normalized path, `..` removes the name before it
+fn normalized_components(path: &Path) -> Vec<Component<'_>> {
+    let mut components: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                components.pop();
+            }
+            _ => components.push(component),
+        }
+    }
+    components
+}
+
+/// Checks the components of the lexically normalized path, so `a/.b/..` is not hidden
 pub fn is_any_component_hidden(path: &PathBuf) -> AreiaResult<bool> {
-    Ok(path
-        .components()
-        .any(|c| is_component_hidden(c.as_os_str())))
+    Ok(normalized_components(path)
+        .into_iter()
+        .any(is_component_hidden))
 }
 
 /// Takes in a path, and returns a new path where the last component is hidden.
 /// Returns the path unmodified if the last component is already hidden
-pub fn make_hidden_path(path: &PathBuf) -> PathBuf {
+/// Works on the bytes of the name, so any valid Unix file name can be hidden
+pub fn make_hidden_path(path: &PathBuf) -> AreiaResult<PathBuf> {
     let mut new_path = path.clone();
-    if is_any_component_hidden(&new_path).expect("Always Ok") {
-        new_path
-    } else {
-        new_path.set_file_name(format!(
-            ".{}",
-            new_path
-                .file_name()
-                .expect("Path must have a file name")
-                .to_str()
-                .expect("Unix path is valid UTF-8 by convention")
-        ));
-        new_path
+    if is_any_component_hidden(&new_path)? {
+        return Ok(new_path);
     }
+    let Some(name) = new_path.file_name() else {
+        return Err(AreiaError::PathMustBeSomething(path.clone()));
+    };
+    let mut hidden_name = Vec::with_capacity(name.len() + 1);
+    hidden_name.push(b'.');
+    hidden_name.extend_from_slice(name.as_bytes());
+    new_path.set_file_name(OsString::from_vec(hidden_name));
+    Ok(new_path)
 }
 
 pub fn make_unhidden_path(path: &PathBuf) -> AreiaResult<PathBuf> {
@@ -90,14 +263,14 @@ pub fn make_unhidden_path(path: &PathBuf) -> AreiaResult<PathBuf> {
 /// If yes -> returns the path and creates the file if not present
 /// If no -> moves the last component to be hidden, returns the new path
 pub fn hide_file(path: &PathBuf) -> AreiaResult<PathBuf> {
-    if is_any_component_hidden(&path).expect("Always Ok") {
+    if is_any_component_hidden(&path)? {
         if !path.exists() {
             create_all_dir_with_file(&path)?;
         }
         return Ok(path.clone());
     }
     let old_path = path.clone();
-    let new_path = make_hidden_path(&path);
+    let new_path = make_hidden_path(&path)?;
     if !old_path.exists() {
         create_all_dir_with_file(&old_path)?;
     }
@@ -107,12 +280,12 @@ pub fn hide_file(path: &PathBuf) -> AreiaResult<PathBuf> {
 
 /// First checks if path points inside a hidden system folder.
 /// If yes, returns an Error
-/// If no, moves the first hidden component to be unhidden and returns the new path
+/// If no, moves every hidden component of the normalized path to be unhidden and returns the
+/// new path
 pub fn unhide_file(path: &PathBuf) -> AreiaResult<PathBuf> {
     let old_path = path.clone();
-    let mut new_path = path.clone();
 
-    if !is_any_component_hidden(&old_path).expect("Always Ok") {
+    if !is_any_component_hidden(&old_path)? {
         return Ok(old_path);
     }
 
@@ -123,7 +296,7 @@ pub fn unhide_file(path: &PathBuf) -> AreiaResult<PathBuf> {
     //     - Of all `BaseDir` only runtime and home is not hidden
     //     - Of all `UserDir` only font is hidden
     if cfg!(target_os = "linux") {
-        let base_dirs = BaseDirs::new()?;
+        let base_dirs = base_dirs()?;
         let mut system_dirs = Vec::new();
 
         system_dirs.push(base_dirs.cache_dir());
@@ -146,28 +319,28 @@ pub fn unhide_file(path: &PathBuf) -> AreiaResult<PathBuf> {
         }
     }
 
-    // check for the first hidden component, remove the leading dot, reconstruct the path
-    let mut new_components = Vec::new();
-    for component in new_path.components() {
-        if is_component_hidden(component.as_os_str()) {
-            let unhidden_component = component
-                .as_os_str()
-                .to_str()
-                .expect("Unix path is valiud UTF-8 by convention")
-                .strip_prefix(".")
-                .expect("Component must be hidden");
-            let tmp = OsString::from(unhidden_component);
-            new_components.push(tmp);
-        } else {
-            new_components.push(component.as_os_str().to_os_string());
+    // Strip the leading dot of each hidden component that survives the normalization, so
+    //      `a/.b/../.c` becomes `a/c`. The deepest one is moved first, so the parents of each move
+    //      still have their old names
+    let normalized = normalized_components(path);
+    let mut components: Vec<OsString> = normalized
+        .iter()
+        .map(|component| component.as_os_str().to_os_string())
+        .collect();
+    let exists = PathBuf::from_iter(&components).symlink_metadata().is_ok();
+    for (index, component) in normalized.into_iter().enumerate().rev() {
+        if !is_component_hidden(component) {
+            continue;
+        }
+        // Hidden components start with a `.`, strip it from the bytes of the name
+        let name = component.as_os_str().as_bytes();
+        let from = PathBuf::from_iter(&components[..=index]);
+        components[index] = OsStr::from_bytes(&name[1..]).to_os_string();
+        if exists {
+            atomic_move(&from, &PathBuf::from_iter(&components[..=index]))?;
         }
     }
-    new_path = PathBuf::from_iter(new_components);
-
-    if old_path.exists() {
-        atomic_move(&old_path, &new_path)?;
-    }
-    return Ok(new_path);
+    Ok(PathBuf::from_iter(components))
 }
 
 // I suspect this wont work as I expect it to - especially with several nested dirs
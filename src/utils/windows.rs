@@ -1,6 +1,7 @@
-use crate::BaseDirs;
+use crate::base_dirs;
 use crate::error::{AreiaError, AreiaResult};
-use std::path::PathBuf;
+use crate::{DirKind, DirSource, RuntimeDirViolation, User, UserInfo};
+use std::path::{Path, PathBuf};
 
 use super::ffi::windows;
 
@@ -24,6 +25,77 @@ pub fn get_home() -> AreiaResult<PathBuf> {
     }
 }
 
+/// Get where the home directory of `get_home()` comes from
+pub fn get_home_source() -> DirSource {
+    if windows::get_path(windows::FolderID::Profile).is_ok() {
+        DirSource::Default
+    } else if std::env::var_os("USERPROFILE").is_some() {
+        DirSource::EnvVar("USERPROFILE".to_owned())
+    } else {
+        DirSource::Default
+    }
+}
+
+/// The known folders are only available for the current user
+pub fn get_user_home(_user: &User) -> AreiaResult<PathBuf> {
+    Err(AreiaError::OtherUsersNotSupported(
+        "No passwd database on Windows".to_string(),
+    ))
+}
+
+pub fn get_user_info(_user: &User) -> AreiaResult<UserInfo> {
+    Err(AreiaError::OtherUsersNotSupported(
+        "No passwd database on Windows".to_string(),
+    ))
+}
+
+pub fn get_current_user_info() -> AreiaResult<UserInfo> {
+    Err(AreiaError::OtherUsersNotSupported(
+        "No passwd database on Windows".to_string(),
+    ))
+}
+
+/// There is no `sudo` on Windows, so no invoking user to act for
+pub fn is_privileged() -> bool {
+    false
+}
+
+pub fn change_owner_to(_path: &Path, _user: &User) -> AreiaResult<()> {
+    Err(AreiaError::OtherUsersNotSupported(
+        "No passwd database on Windows".to_string(),
+    ))
+}
+
+/// Creates the file `path` if it does not exist yet
+///
+/// Returns `false` if the file already existed, it is left untouched then.
+pub fn create_new_file(path: &Path) -> AreiaResult<bool> {
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+    {
+        Ok(_) => Ok(true),
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Windows has no XDG runtime directory
+pub fn runtime_dir_violations(_path: &Path) -> Vec<RuntimeDirViolation> {
+    Vec::new()
+}
+
+pub fn create_runtime_fallback() -> AreiaResult<PathBuf> {
+    Err(AreiaError::DirectoryNotAvailable(DirKind::Runtime))
+}
+
+/// Windows has no permission bits - the directories inherit the ACL of their parent
+pub fn create_private_dir_all(path: &Path) -> AreiaResult<()> {
+    std::fs::create_dir_all(path)?;
+    Ok(())
+}
+
 pub fn hide_file(path: &mut PathBuf) -> AreiaResult<PathBuf> {
     if !path.exists() {
         return Err(AreiaError::FileDoesNotExist(path.to_path_buf()));
@@ -40,8 +112,8 @@ pub fn unhide_file(path: &mut PathBuf) -> AreiaResult<PathBuf> {
     // need to check if inside hidden directory
     // if yes, do nothing, and return path
     let mut sys_dirs = Vec::new();
-        
-    let base_dirs = BaseDirs::new()?;
+
+    let base_dirs = base_dirs()?;
     sys_dirs.push(base_dirs.cache_dir());
     sys_dirs.push(base_dirs.config_dir());
     sys_dirs.push(base_dirs.config_local_dir());
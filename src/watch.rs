@@ -0,0 +1,441 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc, PoisonError, RwLock,
+        mpsc::{Receiver, channel},
+    },
+};
+
+#[cfg(target_os = "linux")]
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    sync::Mutex,
+    thread::JoinHandle,
+};
+
+#[cfg(target_os = "linux")]
+use crate::utils::inotify::{
+    IN_CLOSE_WRITE, IN_CREATE, IN_DELETE, IN_DELETE_SELF, IN_IGNORED, IN_MOVE_SELF, IN_MOVED_FROM,
+    IN_MOVED_TO, IN_ONLYDIR, IN_Q_OVERFLOW, Inotify, InotifyEvent, WakePipe,
+};
+use crate::{
+    UserDirs,
+    error::{AreiaError, AreiaResult},
+    refresh,
+};
+
+#[cfg(target_os = "linux")]
+const WATCH_MASK: u32 = IN_CREATE
+    | IN_CLOSE_WRITE
+    | IN_DELETE
+    | IN_DELETE_SELF
+    | IN_MOVED_FROM
+    | IN_MOVED_TO
+    | IN_MOVE_SELF
+    | IN_ONLYDIR;
+
+/// A change reported by a `Watcher`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// A file or directory was created
+    Created(PathBuf),
+    /// A file opened for writing was closed, i.e. it was probably changed
+    Modified(PathBuf),
+    /// A file or directory was deleted
+    Deleted(PathBuf),
+    /// A file or directory was moved away or renamed from this path
+    MovedFrom(PathBuf),
+    /// A file or directory was moved or renamed to this path
+    MovedTo(PathBuf),
+    /// Events were lost because the queue of the kernel overflowed, re-read everything
+    Overflow,
+}
+
+impl WatchEvent {
+    /// Get the path the event is about
+    ///
+    /// Returns `None` for `WatchEvent::Overflow`.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            WatchEvent::Created(path)
+            | WatchEvent::Modified(path)
+            | WatchEvent::Deleted(path)
+            | WatchEvent::MovedFrom(path)
+            | WatchEvent::MovedTo(path) => Some(path),
+            WatchEvent::Overflow => None,
+        }
+    }
+}
+
+/// A watched directory and the names inside it events are reported for
+#[cfg(target_os = "linux")]
+struct Watch {
+    dir: PathBuf,
+    /// `None` reports every entry of the directory
+    names: Option<HashSet<OsString>>,
+}
+
+/// Watches files and directories for changes
+///
+/// Events are delivered on a background thread, either to a callback (`Watcher::new()`) or
+/// through a channel (`Watcher::channel()`).
+/// Watching a directory reports changes of its direct entries, it is not recursive.
+/// Watching a file watches its parent directory, so the file is still followed when an editor
+/// replaces it by renaming a new file over it.
+///
+/// The background thread stops when the `Watcher` is dropped.
+///
+/// | Linux | Windows | macOS |
+/// | --- | --- | --- |
+/// | inotify | Error | Error |
+///
+/// # Example
+///
+/// ```no_run
+/// use areia::{ProjectDirs, Watcher};
+///
+/// let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+/// let (mut watcher, events) = Watcher::channel().unwrap();
+/// watcher.watch(project.config_dir()).unwrap();
+/// for event in events {
+///     println!("{event:?}, reloading settings");
+/// }
+/// ```
+pub struct Watcher {
+    #[cfg(target_os = "linux")]
+    inotify: Arc<Inotify>,
+    #[cfg(target_os = "linux")]
+    wake: Arc<WakePipe>,
+    #[cfg(target_os = "linux")]
+    watches: Arc<Mutex<HashMap<i32, Watch>>>,
+    #[cfg(target_os = "linux")]
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watcher {
+    /// Create a new `Watcher` that calls `callback` with every event
+    ///
+    /// The callback runs on the background thread of the watcher.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::Watcher;
+    ///
+    /// let watcher = Watcher::new(|event| println!("{event:?}"));
+    /// if cfg!(target_os = "linux") {
+    ///     assert!(watcher.is_ok());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::WatchingNotSupported` if the OS is not supported or an `AreiaError`
+    /// if the OS error occurred
+    #[cfg(target_os = "linux")]
+    pub fn new<F: FnMut(WatchEvent) + Send + 'static>(mut callback: F) -> AreiaResult<Watcher> {
+        let inotify = Arc::new(Inotify::new()?);
+        let wake = Arc::new(WakePipe::new()?);
+        let watches: Arc<Mutex<HashMap<i32, Watch>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        let thread = {
+            let inotify = Arc::clone(&inotify);
+            let wake = Arc::clone(&wake);
+            let watches = Arc::clone(&watches);
+            std::thread::spawn(move || {
+                // An error of the inotify instance can't be recovered from, the thread stops
+                while let Ok(true) = inotify.wait(&wake) {
+                    let Ok(events) = inotify.read_events() else {
+                        return;
+                    };
+                    for event in events {
+                        let watch_event = {
+                            let mut watches =
+                                watches.lock().unwrap_or_else(PoisonError::into_inner);
+                            watch_event(&mut watches, event)
+                        };
+                        if let Some(watch_event) = watch_event {
+                            callback(watch_event);
+                        }
+                    }
+                }
+            })
+        };
+        Ok(Watcher {
+            inotify,
+            wake,
+            watches,
+            thread: Some(thread),
+        })
+    }
+
+    /// Create a new `Watcher` that calls `callback` with every event
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::WatchingNotSupported` if the OS is not supported
+    #[cfg(not(target_os = "linux"))]
+    pub fn new<F: FnMut(WatchEvent) + Send + 'static>(_callback: F) -> AreiaResult<Watcher> {
+        Err(AreiaError::WatchingNotSupported(
+            "Watching is only supported on Linux".to_string(),
+        ))
+    }
+
+    /// Create a new `Watcher` that sends every event to the returned `Receiver`
+    ///
+    /// The `Receiver` is disconnected once the `Watcher` is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use areia::Watcher;
+    ///
+    /// if cfg!(target_os = "linux") {
+    ///     let (watcher, events) = Watcher::channel().unwrap();
+    ///     drop(watcher);
+    ///     assert!(events.recv().is_err());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See `Watcher::new()`
+    pub fn channel() -> AreiaResult<(Watcher, Receiver<WatchEvent>)> {
+        let (sender, receiver) = channel();
+        let watcher = Watcher::new(move |event| {
+            // The receiver may be gone already, the events are of no interest then
+            let _ = sender.send(event);
+        })?;
+        Ok((watcher, receiver))
+    }
+
+    /// Start watching `path`
+    ///
+    /// A directory is watched with all its direct entries.
+    /// A file is watched by watching its parent directory, which has to exist - the file itself
+    /// may not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::PathMustBeSomething` if a file has no parent directory,
+    /// `AreiaError::WatchingNotSupported` if the OS is not supported or the OS error if the
+    /// path can't be watched, e.g. because it does not exist
+    pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> AreiaResult<()> {
+        #[cfg(target_os = "linux")]
+        {
+            let path = path.as_ref();
+            let (dir, name) = split_watch_path(path)?;
+            let wd = self.inotify.add_watch(&dir, WATCH_MASK)?;
+            let mut watches = self.watches.lock().unwrap_or_else(PoisonError::into_inner);
+            let watch = watches.entry(wd).or_insert_with(|| Watch {
+                dir,
+                names: Some(HashSet::new()),
+            });
+            match (name, &mut watch.names) {
+                (None, names) => *names = None,
+                (Some(name), Some(names)) => {
+                    names.insert(name);
+                }
+                (Some(_), None) => {}
+            }
+            Ok(())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = path;
+            Err(AreiaError::WatchingNotSupported(
+                "Watching is only supported on Linux".to_string(),
+            ))
+        }
+    }
+
+    /// Stop watching `path`
+    ///
+    /// Paths that are not watched are ignored, as well as files inside a directory that is
+    /// watched as a whole - unwatch the directory instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the watch can't be removed
+    pub fn unwatch<P: AsRef<Path>>(&mut self, path: P) -> AreiaResult<()> {
+        #[cfg(target_os = "linux")]
+        {
+            let (dir, name) = split_watch_path(path.as_ref())?;
+            let mut watches = self.watches.lock().unwrap_or_else(PoisonError::into_inner);
+            let Some((&wd, watch)) = watches.iter_mut().find(|(_, watch)| watch.dir == dir) else {
+                return Ok(());
+            };
+            match (name, &mut watch.names) {
+                (Some(name), Some(names)) => {
+                    names.remove(&name);
+                    if !names.is_empty() {
+                        return Ok(());
+                    }
+                }
+                // The whole directory is watched, a single entry can't be left out of it
+                (Some(_), None) => return Ok(()),
+                (None, _) => {}
+            }
+            watches.remove(&wd);
+            self.inotify.rm_watch(wd)?;
+            Ok(())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = path;
+            Ok(())
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        {
+            self.wake.wake();
+            if let Some(thread) = self.thread.take() {
+                // A panic of the callback already ended the thread
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+/// Splits `path` into the directory to watch and the name of the watched file inside it
+#[cfg(target_os = "linux")]
+fn split_watch_path(path: &Path) -> AreiaResult<(PathBuf, Option<OsString>)> {
+    if path.is_dir() {
+        return Ok((path.to_path_buf(), None));
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => {
+            let parent = if parent.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                parent
+            };
+            Ok((parent.to_path_buf(), Some(name.to_os_string())))
+        }
+        _ => Err(AreiaError::PathMustBeSomething(path.to_path_buf())),
+    }
+}
+
+/// Translates an inotify event, `None` if it is not of interest
+#[cfg(target_os = "linux")]
+fn watch_event(watches: &mut HashMap<i32, Watch>, event: InotifyEvent) -> Option<WatchEvent> {
+    if event.mask & IN_Q_OVERFLOW != 0 {
+        return Some(WatchEvent::Overflow);
+    }
+    if event.mask & IN_IGNORED != 0 {
+        // The watched directory is gone or the watch was removed
+        watches.remove(&event.wd);
+        return None;
+    }
+    let watch = watches.get(&event.wd)?;
+    let path = match (event.name, &watch.names) {
+        (Some(name), Some(names)) if !names.contains(&name) => return None,
+        (Some(name), _) => watch.dir.join(name),
+        // Changes of the directory itself are only of interest if it is watched as a whole
+        (None, Some(_)) => return None,
+        (None, None) => watch.dir.clone(),
+    };
+    let mask = event.mask;
+    if mask & IN_CREATE != 0 {
+        Some(WatchEvent::Created(path))
+    } else if mask & IN_CLOSE_WRITE != 0 {
+        Some(WatchEvent::Modified(path))
+    } else if mask & (IN_DELETE | IN_DELETE_SELF) != 0 {
+        Some(WatchEvent::Deleted(path))
+    } else if mask & (IN_MOVED_FROM | IN_MOVE_SELF) != 0 {
+        Some(WatchEvent::MovedFrom(path))
+    } else if mask & IN_MOVED_TO != 0 {
+        Some(WatchEvent::MovedTo(path))
+    } else {
+        None
+    }
+}
+
+/// User directories that follow changes of `user-dirs.dirs`
+///
+/// Created with `UserDirs::watch()`.
+/// Every change of the file resolves the user directories again, `LiveUserDirs::get()` always
+/// returns the latest version.
+/// The cache of `user_dirs()` is dropped as well, see `refresh()`.
+///
+/// | Linux | Windows | macOS |
+/// | --- | --- | --- |
+/// | Supported | Error | Error |
+pub struct LiveUserDirs {
+    current: Arc<RwLock<Arc<UserDirs>>>,
+    _watcher: Watcher,
+}
+
+impl LiveUserDirs {
+    /// Get the latest user directories
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use areia::UserDirs;
+    ///
+    /// let live = UserDirs::watch().unwrap();
+    /// let downloads = live.get().download_dir().clone();
+    /// ```
+    #[must_use]
+    pub fn get(&self) -> Arc<UserDirs> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl UserDirs {
+    /// Create user directories that are resolved again whenever `user-dirs.dirs` changes
+    ///
+    /// Useful for long-running processes, e.g. a desktop agent, that should pick up changes
+    /// made with `xdg-user-dirs-update` or a settings dialog without a restart.
+    ///
+    /// | Linux | Windows | macOS |
+    /// | --- | --- | --- |
+    /// | Supported | Error | Error |
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use areia::UserDirs;
+    ///
+    /// let live = UserDirs::watch().unwrap();
+    /// println!("Downloads: {:?}", live.get().download_dir());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `AreiaError::WatchingNotSupported` if the OS has no `user-dirs.dirs` or
+    /// watching is not supported, or an `AreiaError` if the directories could not be resolved
+    /// or the directory of `user-dirs.dirs` does not exist
+    pub fn watch() -> AreiaResult<LiveUserDirs> {
+        let user_dirs = UserDirs::new()?;
+        let file = user_dirs
+            .dirs_file()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| {
+                AreiaError::WatchingNotSupported("No user-dirs.dirs on this OS".to_string())
+            })?;
+        let current = Arc::new(RwLock::new(Arc::new(user_dirs)));
+        let mut watcher = {
+            let current = Arc::clone(&current);
+            Watcher::new(move |_| {
+                // Keep the last good version if the file is broken mid-write
+                if let Ok(user_dirs) = UserDirs::new() {
+                    *current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(user_dirs);
+                    refresh();
+                }
+            })?
+        };
+        watcher.watch(&file)?;
+        Ok(LiveUserDirs {
+            current,
+            _watcher: watcher,
+        })
+    }
+}
@@ -1,4 +1,7 @@
-use areia::BaseDirs;
+use areia::{BaseDirs, DirKind, error::AreiaError};
+
+#[cfg(unix)]
+mod common;
 
 #[test]
 fn create_new_base() {
@@ -94,3 +97,150 @@ fn all_paths_present() {
     assert!(base.runtime_dir().is_none());
     assert!(base.state_dir().is_none());
 }
+
+#[test]
+fn search_paths_start_with_user_dirs() {
+    let base = BaseDirs::new().unwrap();
+
+    let data_dirs: Vec<_> = base.data_dirs().collect();
+    assert!(data_dirs.len() > 1);
+    assert_eq!(data_dirs[0], base.data_dir());
+    assert!(data_dirs.iter().all(|dir| dir.is_absolute()));
+
+    let config_dirs: Vec<_> = base.config_dirs().collect();
+    assert!(config_dirs.len() > 1);
+    assert_eq!(config_dirs[0], base.config_dir());
+    assert!(config_dirs.iter().all(|dir| dir.is_absolute()));
+}
+
+#[test]
+#[cfg(unix)]
+fn find_user_config_file() {
+    let tmp = common::TempDir::new("find");
+    let base = BaseDirs::from_env(&areia::MapEnv::new(tmp.path())).unwrap();
+    let relative = "areia_search_test/config.toml";
+    let user_config = base.config_dir().join(relative);
+
+    assert!(base.find_config_file(relative).unwrap().is_none());
+    assert!(base.find_all_config_files(relative).unwrap().is_empty());
+
+    std::fs::create_dir_all(user_config.parent().unwrap()).unwrap();
+    std::fs::File::create(&user_config).unwrap();
+
+    assert_eq!(
+        base.find_config_file(relative).unwrap(),
+        Some(user_config.clone())
+    );
+    assert_eq!(
+        base.find_all_config_files(relative).unwrap(),
+        vec![user_config.clone()]
+    );
+    assert!(base.find_data_file(relative).unwrap().is_none());
+}
+
+#[test]
+fn find_files_rejects_escaping_paths() {
+    let base = BaseDirs::new().unwrap();
+    for path in [
+        "",
+        "../config.toml",
+        "a/../../config.toml",
+        "/etc/passwd",
+        "./config.toml",
+    ] {
+        assert!(matches!(
+            base.find_config_file(path),
+            Err(AreiaError::PathMustBeSomething(_))
+        ));
+        assert!(base.find_all_config_files(path).is_err());
+        assert!(base.find_data_file(path).is_err());
+    }
+}
+
+#[test]
+fn ensure_creates_dirs() {
+    let base = BaseDirs::new().unwrap();
+    assert!(base.ensure().is_ok());
+
+    assert!(base.cache_dir().is_dir());
+    assert!(base.config_dir().is_dir());
+    assert!(base.config_local_dir().is_dir());
+    assert!(base.data_dir().is_dir());
+    assert!(base.data_local_dir().is_dir());
+    assert!(base.preference_dir().is_dir());
+    if let Some(state_dir) = base.state_dir() {
+        assert!(state_dir.is_dir());
+    }
+}
+
+#[test]
+fn ensure_dir_reports_unavailable_dirs() {
+    let base = BaseDirs::new().unwrap();
+    let cache = base.ensure_dir(DirKind::Cache);
+    assert_eq!(cache.as_ref().ok(), Some(base.cache_dir()));
+    if base.state_dir().is_none() {
+        assert!(base.ensure_dir(DirKind::State).is_err());
+    }
+}
+
+#[test]
+fn ensure_dir_never_creates_runtime_dir() {
+    let base = BaseDirs::new().unwrap();
+    assert!(matches!(
+        base.ensure_dir(DirKind::Runtime),
+        Err(AreiaError::DirectoryManagedBySystem(DirKind::Runtime))
+    ));
+}
+
+#[test]
+fn get_by_kind() {
+    let base = BaseDirs::new().unwrap();
+    assert_eq!(base.get(DirKind::Home), Some(base.home_dir().as_path()));
+    assert_eq!(base.get(DirKind::Cache), Some(base.cache_dir().as_path()));
+    assert_eq!(
+        base.get(DirKind::State),
+        base.state_dir().map(|dir| dir.as_path())
+    );
+    assert!(base.get(DirKind::Download).is_none());
+
+    let kinds = base.iter().map(|(kind, _)| kind).collect::<Vec<_>>();
+    assert_eq!(kinds[..3], [DirKind::Home, DirKind::Cache, DirKind::Config]);
+    assert!(!kinds.contains(&DirKind::Desktop));
+}
+
+#[test]
+fn parse_kind_names() {
+    for kind in DirKind::ALL {
+        assert_eq!(kind.to_string().parse::<DirKind>().unwrap(), kind);
+    }
+    assert_eq!(
+        "config-local".parse::<DirKind>().unwrap(),
+        DirKind::ConfigLocal
+    );
+    assert!("ConfigLocal".parse::<DirKind>().is_err());
+    assert!("".parse::<DirKind>().is_err());
+}
+
+#[test]
+fn cached_base_dirs() {
+    use areia::{base_dirs, refresh, user_dirs};
+    use std::sync::Arc;
+
+    let first = base_dirs().unwrap();
+    assert!(Arc::ptr_eq(&first, &base_dirs().unwrap()));
+    assert_eq!(first.config_dir(), BaseDirs::new().unwrap().config_dir());
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| std::thread::spawn(|| user_dirs().unwrap()))
+        .collect();
+    let user = user_dirs().unwrap();
+    for thread in threads {
+        assert!(Arc::ptr_eq(&user, &thread.join().unwrap()));
+    }
+
+    refresh();
+    let second = base_dirs().unwrap();
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert_eq!(first.home_dir(), second.home_dir());
+    assert!(!Arc::ptr_eq(&user, &user_dirs().unwrap()));
+}
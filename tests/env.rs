@@ -0,0 +1,446 @@
+use areia::{BaseDirs, EnvSource, MapEnv, ProcessEnv, UserDirs, error::AreiaError};
+use std::path::PathBuf;
+
+#[cfg(unix)]
+mod common;
+
+#[test]
+fn map_env_only_knows_its_vars() {
+    let env = MapEnv::new("/home/alice").with_var("XDG_CACHE_HOME", "/tmp/alice");
+    assert_eq!(env.home_dir().unwrap(), PathBuf::from("/home/alice"));
+    assert_eq!(env.var_os("XDG_CACHE_HOME"), Some("/tmp/alice".into()));
+    assert!(env.var_os("HOME").is_none());
+    assert!(env.var_os("PATH").is_none());
+}
+
+#[test]
+fn map_env_needs_absolute_home() {
+    for env in [
+        MapEnv::default(),
+        MapEnv::new(""),
+        MapEnv::new("home/alice"),
+    ] {
+        assert!(matches!(env.home_dir(), Err(AreiaError::CantGetHomeDir)));
+        assert!(matches!(
+            BaseDirs::from_env(&env),
+            Err(AreiaError::CantGetHomeDir)
+        ));
+    }
+}
+
+#[test]
+fn process_env_matches_new() {
+    let from_env = BaseDirs::from_env(&ProcessEnv).unwrap();
+    let new = BaseDirs::new().unwrap();
+    assert_eq!(from_env.home_dir(), new.home_dir());
+    assert_eq!(from_env.cache_dir(), new.cache_dir());
+    assert_eq!(from_env.config_dir(), new.config_dir());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn base_dirs_from_defaults() {
+    let base = BaseDirs::from_env(&MapEnv::new("/home/alice")).unwrap();
+
+    assert_eq!(base.home_dir(), &PathBuf::from("/home/alice"));
+    assert_eq!(base.cache_dir(), &PathBuf::from("/home/alice/.cache"));
+    assert_eq!(base.config_dir(), &PathBuf::from("/home/alice/.config"));
+    assert_eq!(base.data_dir(), &PathBuf::from("/home/alice/.local/share"));
+    assert_eq!(
+        base.state_dir(),
+        Some(&PathBuf::from("/home/alice/.local/state"))
+    );
+    // `~/.local/bin` is only used if it exists
+    assert!(base.executable_dir().is_none());
+    assert!(base.runtime_dir().is_none());
+    assert_eq!(
+        base.data_dirs().collect::<Vec<_>>(),
+        vec![
+            &PathBuf::from("/home/alice/.local/share"),
+            &PathBuf::from("/usr/local/share"),
+            &PathBuf::from("/usr/share")
+        ]
+    );
+    assert_eq!(
+        base.config_dirs().collect::<Vec<_>>(),
+        vec![
+            &PathBuf::from("/home/alice/.config"),
+            &PathBuf::from("/etc/xdg")
+        ]
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn base_dirs_from_vars() {
+    let env = MapEnv::new("/home/alice")
+        .with_var("XDG_CACHE_HOME", "/srv/cache")
+        .with_var("XDG_CONFIG_HOME", "relative/config")
+        .with_var("XDG_RUNTIME_DIR", "/run/user/1000")
+        .with_var("XDG_BIN_HOME", "/srv/bin")
+        .with_var("XDG_DATA_DIRS", "/opt/share:relative/share::/usr/share")
+        .with_var("XDG_CONFIG_DIRS", "");
+    let base = BaseDirs::from_env(&env).unwrap();
+
+    assert_eq!(base.cache_dir(), &PathBuf::from("/srv/cache"));
+    // Relative paths are ignored, as mandated by the XDG spec
+    assert_eq!(base.config_dir(), &PathBuf::from("/home/alice/.config"));
+    assert_eq!(base.runtime_dir(), Some(&PathBuf::from("/run/user/1000")));
+    assert_eq!(base.executable_dir(), Some(&PathBuf::from("/srv/bin")));
+    assert_eq!(
+        base.data_dirs().skip(1).collect::<Vec<_>>(),
+        vec![&PathBuf::from("/opt/share"), &PathBuf::from("/usr/share")]
+    );
+    assert_eq!(
+        base.config_dirs().skip(1).collect::<Vec<_>>(),
+        vec![&PathBuf::from("/etc/xdg")]
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn user_dirs_from_env() {
+    let home = ProcessEnv.home_dir().unwrap();
+    let user = UserDirs::from_env(&MapEnv::new(&home)).unwrap();
+    assert_eq!(user.home_dir(), &home);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn explain_base_dirs() {
+    use areia::{DirKind, DirSource};
+    use std::ffi::OsStr;
+
+    let env = MapEnv::new("/home/alice")
+        .with_var("XDG_DATA_HOME", "/srv/data")
+        .with_var("XDG_STATE_HOME", "state")
+        .with_var("XDG_CACHE_HOME", "");
+    let base = BaseDirs::from_env(&env).unwrap();
+    let record = |kind| {
+        base.explain()
+            .iter()
+            .find(|record| record.kind() == kind)
+            .unwrap()
+    };
+
+    assert_eq!(base.explain().len(), 10);
+    assert_eq!(record(DirKind::Home).source(), &DirSource::Supplied);
+    let data = record(DirKind::Data);
+    assert_eq!(data.path(), Some(PathBuf::from("/srv/data").as_path()));
+    assert_eq!(
+        data.source(),
+        &DirSource::EnvVar("XDG_DATA_HOME".to_owned())
+    );
+    assert_eq!(
+        record(DirKind::DataLocal).source(),
+        &DirSource::EnvVar("XDG_DATA_HOME".to_owned())
+    );
+    let state = record(DirKind::State);
+    assert_eq!(state.source(), &DirSource::Default);
+    assert_eq!(
+        state.rejected(),
+        Some(("XDG_STATE_HOME", OsStr::new("state")))
+    );
+    assert_eq!(
+        state.to_string(),
+        "state: /home/alice/.local/state (default), ignored relative `XDG_STATE_HOME=state`"
+    );
+    // Empty variables are unset, not rejected
+    assert!(record(DirKind::Cache).rejected().is_none());
+    let runtime = record(DirKind::Runtime);
+    assert!(runtime.path().is_none());
+    assert_eq!(runtime.to_string(), "runtime: not available");
+}
+
+#[test]
+fn process_env_home_source() {
+    use areia::{DirKind, DirSource};
+
+    let base = BaseDirs::new().unwrap();
+    let source = base.source(DirKind::Home).unwrap();
+    if cfg!(unix) && std::env::var_os("HOME").is_some() {
+        assert_eq!(source, &DirSource::EnvVar("HOME".to_owned()));
+    }
+    assert_ne!(source, &DirSource::Supplied);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn validate_runtime_dir() {
+    use areia::{RuntimeDirViolation, error::AreiaError};
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    let unset = BaseDirs::from_env(&MapEnv::new("/home/alice")).unwrap();
+    assert!(matches!(
+        unset.validate_runtime_dir(),
+        Err(AreiaError::DirectoryNotAvailable(_))
+    ));
+    let fallback = unset.runtime_dir_or_fallback().unwrap();
+    assert!(fallback.is_dir());
+    assert_eq!(
+        fs::metadata(&fallback).unwrap().permissions().mode() & 0o777,
+        0o700
+    );
+
+    let tmp = common::TempDir::new("runtime");
+    let open = tmp.path().to_path_buf();
+    fs::set_permissions(&open, fs::Permissions::from_mode(0o755)).unwrap();
+    let base =
+        BaseDirs::from_env(&MapEnv::new("/home/alice").with_var("XDG_RUNTIME_DIR", &open)).unwrap();
+    match base.runtime_dir_or_fallback() {
+        Err(AreiaError::InvalidRuntimeDir(path, violations)) => {
+            assert_eq!(path, open);
+            assert_eq!(violations, vec![RuntimeDirViolation::WrongMode(0o755)]);
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+    fs::set_permissions(&open, fs::Permissions::from_mode(0o700)).unwrap();
+    assert_eq!(base.validate_runtime_dir().unwrap(), &open);
+
+    let missing = BaseDirs::from_env(
+        &MapEnv::new("/home/alice").with_var("XDG_RUNTIME_DIR", "/nonexistent/runtime"),
+    )
+    .unwrap();
+    match missing.validate_runtime_dir() {
+        Err(AreiaError::InvalidRuntimeDir(_, violations)) => {
+            assert_eq!(violations, vec![RuntimeDirViolation::NotADirectory]);
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn dirs_for_other_user() {
+    use areia::{DirKind, DirSource, User, error::AreiaError};
+
+    let by_name = BaseDirs::for_user("root").unwrap();
+    let by_uid = BaseDirs::for_user(0).unwrap();
+    assert_eq!(by_name.home_dir(), by_uid.home_dir());
+    assert_eq!(by_name.source(DirKind::Home), Some(&DirSource::Passwd));
+    if cfg!(target_os = "linux") {
+        assert_eq!(by_name.config_dir(), &by_name.home_dir().join(".config"));
+        assert!(by_name.runtime_dir().is_none());
+    }
+    let user = UserDirs::for_user(User::Uid(0)).unwrap();
+    assert_eq!(user.home_dir(), by_name.home_dir());
+
+    match BaseDirs::for_user("areia-no-such-user") {
+        Err(AreiaError::UserNotFound(user)) => {
+            assert_eq!(user, User::Name("areia-no-such-user".to_owned()));
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+    assert!(UserDirs::for_user("bad\0name").is_err());
+}
+
+#[test]
+fn detect_invoking_user() {
+    use areia::User;
+
+    let sudo = MapEnv::new("/root")
+        .with_var("SUDO_USER", "alice")
+        .with_var("SUDO_UID", "1000");
+    assert_eq!(User::invoking_from(&sudo), Some(User::Uid(1000)));
+
+    let sudo = MapEnv::new("/root")
+        .with_var("SUDO_USER", "alice")
+        .with_var("SUDO_UID", "not a uid");
+    assert_eq!(
+        User::invoking_from(&sudo),
+        Some(User::Name("alice".to_owned()))
+    );
+
+    let pkexec = MapEnv::new("/root")
+        .with_var("SUDO_USER", "")
+        .with_var("PKEXEC_UID", "1001");
+    assert_eq!(User::invoking_from(&pkexec), Some(User::Uid(1001)));
+
+    assert_eq!(User::invoking_from(&MapEnv::new("/root")), None);
+
+    if User::invoking().is_none() {
+        let base = BaseDirs::for_invoking_user().unwrap();
+        assert_eq!(base.home_dir(), BaseDirs::new().unwrap().home_dir());
+        let user = UserDirs::for_invoking_user().unwrap();
+        assert_eq!(user.home_dir(), base.home_dir());
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn create_files_for_user() {
+    use areia::{User, auto_creator_for, auto_deletor};
+    use std::os::unix::fs::MetadataExt;
+
+    let tmp = common::TempDir::new("chown");
+    let root = tmp.path().to_path_buf();
+    let owner = User::Uid(std::fs::metadata(&root).unwrap().uid());
+
+    let file = root.join("nested/dir/file.txt");
+    auto_creator_for(&file, &owner).unwrap();
+    assert!(file.exists());
+    owner.chown(&file).unwrap();
+
+    // An existing file keeps its content
+    std::fs::write(&file, "settings").unwrap();
+    auto_creator_for(&file, &owner).unwrap();
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "settings");
+
+    // A planted symlink is not followed
+    let link = root.join("nested/link.txt");
+    std::os::unix::fs::symlink(&file, &link).unwrap();
+    assert!(auto_creator_for(&link, &owner).is_err());
+    let dangling = root.join("nested/dangling.txt");
+    std::os::unix::fs::symlink(root.join("nested/target.txt"), &dangling).unwrap();
+    assert!(auto_creator_for(&dangling, &owner).is_err());
+    assert!(!root.join("nested/target.txt").exists());
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "settings");
+
+    assert!(matches!(
+        User::from("areia-no-such-user").chown(&file),
+        Err(areia::error::AreiaError::UserNotFound(_))
+    ));
+    assert!(auto_deletor(&root).is_ok());
+}
+
+#[test]
+#[cfg(unix)]
+fn look_up_user_info() {
+    use areia::{User, UserInfo, error::AreiaError};
+
+    let root = UserInfo::lookup("root").unwrap();
+    assert_eq!(root.uid(), 0);
+    assert_eq!(root.name(), "root");
+    assert_eq!(root.user(), User::Uid(0));
+    assert_eq!(&root, &UserInfo::lookup(0).unwrap());
+    assert_eq!(root.home(), BaseDirs::for_user(0).unwrap().home_dir());
+    assert!(root.shell().is_absolute());
+
+    let me = UserInfo::current().unwrap();
+    assert_eq!(&me, &UserInfo::lookup(me.name()).unwrap());
+
+    assert!(matches!(
+        UserInfo::lookup(4_000_000_000),
+        Err(AreiaError::UserNotFound(User::Uid(4_000_000_000)))
+    ));
+    assert!(matches!(
+        UserInfo::lookup("bad\0name"),
+        Err(AreiaError::UserNotFound(_))
+    ));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn detect_sandboxes() {
+    use areia::{DirKind, DirSource, SandboxKind};
+
+    let flatpak = MapEnv::new("/home/alice")
+        .with_var("FLATPAK_ID", "org.example.App")
+        .with_var("XDG_DATA_HOME", "/home/alice/.var/app/org.example.App/data")
+        .with_var(
+            "XDG_CONFIG_HOME",
+            "/home/alice/.var/app/org.example.App/config",
+        )
+        .with_var("HOST_XDG_CONFIG_HOME", "/srv/alice/config")
+        .with_var("XDG_DATA_DIRS", "/app/share:/usr/share");
+    let base = BaseDirs::from_env(&flatpak).unwrap();
+    let sandbox = base.sandbox().unwrap();
+    assert_eq!(sandbox.kind(), SandboxKind::Flatpak);
+    assert_eq!(sandbox.app_id(), Some("org.example.App"));
+    assert!(sandbox.user_common_dir().is_none());
+    let host = base.host_dirs();
+    assert!(host.sandbox().is_none());
+    assert_eq!(host.home_dir(), &PathBuf::from("/home/alice"));
+    assert_eq!(host.config_dir(), &PathBuf::from("/srv/alice/config"));
+    assert_eq!(host.data_dir(), &PathBuf::from("/home/alice/.local/share"));
+    assert_eq!(
+        host.data_dirs().collect::<Vec<_>>(),
+        vec![
+            &PathBuf::from("/home/alice/.local/share"),
+            &PathBuf::from("/usr/local/share"),
+            &PathBuf::from("/usr/share")
+        ]
+    );
+
+    let snap = MapEnv::new("/home/alice/snap/app/42")
+        .with_var("SNAP", "/snap/app/42")
+        .with_var("SNAP_NAME", "app")
+        .with_var("SNAP_REAL_HOME", "/home/alice")
+        .with_var("SNAP_USER_COMMON", "/home/alice/snap/app/common")
+        .with_var("XDG_CONFIG_HOME", "/home/alice/snap/app/42/.config")
+        .with_var("XDG_DATA_HOME", "/home/alice/snap/app/42/.local/share")
+        .with_var("XDG_RUNTIME_DIR", "/run/user/1000/snap.app");
+    let base = BaseDirs::from_env(&snap).unwrap();
+    let sandbox = base.sandbox().unwrap();
+    assert_eq!(sandbox.kind(), SandboxKind::Snap);
+    assert_eq!(sandbox.app_id(), Some("app"));
+    assert_eq!(
+        sandbox.user_common_dir(),
+        Some(&PathBuf::from("/home/alice/snap/app/common"))
+    );
+    assert_eq!(
+        base.config_dir(),
+        &PathBuf::from("/home/alice/snap/app/42/.config")
+    );
+    let host = sandbox.host_dirs();
+    assert_eq!(host.config_dir(), &PathBuf::from("/home/alice/.config"));
+    assert_eq!(host.data_dir(), &PathBuf::from("/home/alice/.local/share"));
+    assert_eq!(host.runtime_dir(), Some(&PathBuf::from("/run/user/1000")));
+    assert_eq!(
+        host.source(DirKind::Home),
+        Some(&DirSource::EnvVar("SNAP_REAL_HOME".to_owned()))
+    );
+
+    let native = BaseDirs::from_env(&MapEnv::new("/home/alice")).unwrap();
+    assert!(native.sandbox().is_none());
+    assert_eq!(native.host_dirs().config_dir(), native.config_dir());
+}
+
+#[test]
+#[cfg(unix)]
+fn portable_mode() {
+    use areia::{DirKind, DirSource, ProjectDirs};
+
+    let root = PathBuf::from("/media/usb/tool");
+    let base = BaseDirs::portable(&root).unwrap();
+    assert_eq!(base.portable_root(), Some(&root));
+    assert_eq!(base.home_dir(), &root);
+    assert_eq!(base.cache_dir(), &root.join("cache"));
+    assert_eq!(base.config_dir(), &root.join("config"));
+    assert_eq!(base.data_local_dir(), &root.join("data"));
+    assert_eq!(base.state_dir(), Some(&root.join("state")));
+    assert_eq!(base.executable_dir(), Some(&root.join("bin")));
+    assert!(base.runtime_dir().is_none());
+    assert!(base.sandbox().is_none());
+    assert_eq!(
+        base.config_dirs().collect::<Vec<_>>(),
+        vec![&root.join("config")]
+    );
+    assert!(base.iter().all(|(_, path)| path.starts_with(&root)));
+    assert_eq!(
+        base.source(DirKind::Cache),
+        Some(&DirSource::Portable(root.clone()))
+    );
+    assert!(BaseDirs::portable("relative/root").is_err());
+
+    // The environment variable wins over everything else
+    let env = MapEnv::new("/home/alice")
+        .with_var("AREIA_PORTABLE_ROOT", "/media/usb/tool")
+        .with_var("XDG_CONFIG_HOME", "/home/alice/.config")
+        .with_var("FLATPAK_ID", "org.example.App");
+    let from_env = BaseDirs::from_env(&env).unwrap();
+    assert_eq!(from_env.portable_root(), Some(&root));
+    assert_eq!(from_env.config_dir(), &root.join("config"));
+    assert!(from_env.sandbox().is_none());
+
+    let relative = MapEnv::new("/home/alice").with_var("AREIA_PORTABLE_ROOT", "tool");
+    assert!(
+        BaseDirs::from_env(&relative)
+            .unwrap()
+            .portable_root()
+            .is_none()
+    );
+
+    let project = ProjectDirs::portable(&root, "com", "Foo Corp", "Bar App").unwrap();
+    assert!(project.data_dir().starts_with(root.join("data")));
+}
@@ -0,0 +1,61 @@
+use areia::{BaseDirs, Exclude, Excludes, MapEnv, error::AreiaError, excluded_from, mark_excluded};
+
+mod common;
+
+#[test]
+fn mark_directories_excluded() {
+    let tmp = common::TempDir::new("exclude-mark");
+    // Marking creates the directory
+    let dir = tmp.path().join("cache");
+    assert!(excluded_from(&dir).is_empty());
+
+    mark_excluded(&dir, Exclude::Backup | Exclude::Indexing | Exclude::Media).unwrap();
+    let tag = std::fs::read_to_string(dir.join("CACHEDIR.TAG")).unwrap();
+    assert!(tag.starts_with("Signature: 8a477f597d28d172789f06886806bc55"));
+    assert!(dir.join(".nomedia").is_file());
+    assert!(dir.join(".trackerignore").is_file());
+    assert!(dir.join(".nosearch").is_file());
+    assert_eq!(excluded_from(&dir), Excludes::all());
+    assert_eq!(excluded_from(dir.join("nested/file")), Excludes::all());
+
+    // Marking again keeps the markers
+    mark_excluded(&dir, Exclude::Backup).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("CACHEDIR.TAG")).unwrap(),
+        tag
+    );
+}
+
+#[test]
+fn detect_only_valid_markers() {
+    let tmp = common::TempDir::new("exclude-detect");
+    let dir = tmp.path();
+    std::fs::write(dir.join("CACHEDIR.TAG"), "not a tag\n").unwrap();
+    std::fs::File::create(dir.join(".nosearch")).unwrap();
+    assert_eq!(excluded_from(dir), Excludes::from(Exclude::Indexing));
+
+    // An invalid tag is replaced
+    mark_excluded(dir, Exclude::Backup).unwrap();
+    assert!(excluded_from(dir).contains(Exclude::Backup));
+
+    let file = dir.join("file");
+    std::fs::File::create(&file).unwrap();
+    assert!(matches!(
+        mark_excluded(&file, Exclude::Media),
+        Err(AreiaError::NotADirectory(_))
+    ));
+}
+
+#[test]
+fn ensure_app_cache_dir() {
+    let home = common::TempDir::new("exclude-home");
+    let base = BaseDirs::from_env(&MapEnv::new(home.path())).unwrap();
+
+    let cache = base.ensure_app_cache_dir("app").unwrap();
+    assert_eq!(cache, base.cache_dir().join("app"));
+    assert_eq!(excluded_from(&cache), Excludes::all());
+    assert!(excluded_from(base.cache_dir()).is_empty());
+
+    assert!(base.ensure_app_cache_dir("").is_err());
+    assert!(base.ensure_app_cache_dir("../app").is_err());
+}
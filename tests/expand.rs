@@ -0,0 +1,111 @@
+use areia::{
+    BaseDirs, MapEnv, contract_path_with, error::AreiaError, expand_path, expand_path_with,
+};
+use std::path::PathBuf;
+
+#[test]
+fn expand_tilde_and_variables() {
+    let env = MapEnv::new("/home/alice")
+        .with_var("PROJECT", "areia")
+        .with_var("EMPTY", "");
+
+    let expand = |path: &str| expand_path_with(path, &env).unwrap();
+    assert_eq!(expand("~"), PathBuf::from("/home/alice"));
+    assert_eq!(
+        expand("~/Downloads/foo"),
+        PathBuf::from("/home/alice/Downloads/foo")
+    );
+    assert_eq!(
+        expand("$HOME/src/$PROJECT"),
+        PathBuf::from("/home/alice/src/areia")
+    );
+    assert_eq!(
+        expand("/srv/${PROJECT}_data"),
+        PathBuf::from("/srv/areia_data")
+    );
+    assert_eq!(expand("/srv/$EMPTY/x"), PathBuf::from("/srv//x"));
+    // Only a leading `~` is expanded, a lone or unterminated `$` is kept
+    assert_eq!(
+        expand("/srv/~/cost$/${x"),
+        PathBuf::from("/srv/~/cost$/${x")
+    );
+
+    assert!(matches!(
+        expand_path_with("$AREIA_UNSET/x", &env),
+        Err(AreiaError::UndefinedVariable(name)) if name == "AREIA_UNSET"
+    ));
+    assert!(matches!(
+        expand_path_with("~areia-no-such-user/x", &env),
+        Err(AreiaError::UserNotFound(_)) | Err(AreiaError::OtherUsersNotSupported(_))
+    ));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn expand_xdg_variables_like_base_dirs() {
+    let env = MapEnv::new("/home/alice")
+        .with_var("XDG_CONFIG_HOME", "/srv/alice/config")
+        .with_var("XDG_CACHE_HOME", "relative/cache");
+    let expand = |path: &str| expand_path_with(path, &env).unwrap();
+
+    assert_eq!(
+        expand("$XDG_CONFIG_HOME/app"),
+        PathBuf::from("/srv/alice/config/app")
+    );
+    // Relative and unset values fall back to the default, like `BaseDirs`
+    assert_eq!(
+        expand("$XDG_CACHE_HOME"),
+        PathBuf::from("/home/alice/.cache")
+    );
+    assert_eq!(
+        expand("${XDG_STATE_HOME}/log"),
+        PathBuf::from("/home/alice/.local/state/log")
+    );
+    assert!(expand("$XDG_DOWNLOAD_DIR").starts_with("/home/alice"));
+    assert!(matches!(
+        expand_path_with("$XDG_RUNTIME_DIR", &env),
+        Err(AreiaError::DirectoryNotAvailable(_))
+    ));
+    assert_eq!(expand("~root"), expand_path("~root").unwrap());
+}
+
+#[test]
+fn contract_home() {
+    let env = MapEnv::new("/home/alice");
+    assert_eq!(contract_path_with("/home/alice", &env), PathBuf::from("~"));
+    assert_eq!(
+        contract_path_with("/home/alice/notes/todo.md", &env),
+        PathBuf::from("~").join("notes/todo.md")
+    );
+    assert_eq!(
+        contract_path_with("/home/alicia/notes", &env),
+        PathBuf::from("/home/alicia/notes")
+    );
+    assert_eq!(
+        contract_path_with("/etc/hosts", &MapEnv::new("/")),
+        PathBuf::from("/etc/hosts")
+    );
+
+    let path = PathBuf::from("/home/alice/notes");
+    assert_eq!(
+        expand_path_with(contract_path_with(&path, &env), &env).unwrap(),
+        path
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn expand_home_like_base_dirs_in_portable_mode() {
+    let env = MapEnv::new("/home/alice").with_var("AREIA_PORTABLE_ROOT", "/media/usb/tool");
+    let base = BaseDirs::from_env(&env).unwrap();
+    let expand = |path: &str| expand_path_with(path, &env).unwrap();
+
+    assert_eq!(expand("~/notes"), base.home_dir().join("notes"));
+    assert_eq!(expand("$HOME"), *base.home_dir());
+    assert_eq!(expand("$XDG_CONFIG_HOME"), *base.config_dir());
+    assert!(expand("~").starts_with("/media/usb/tool"));
+    assert_eq!(
+        contract_path_with(base.config_dir(), &env),
+        PathBuf::from("~").join(base.config_dir().strip_prefix(base.home_dir()).unwrap())
+    );
+}
@@ -2,6 +2,9 @@ use areia::{BaseDirs, Hidden};
 use std::fs::{remove_dir, remove_dir_all, remove_file};
 use std::path::PathBuf;
 
+#[cfg(unix)]
+mod common;
+
 #[test]
 fn hide_non_existent_path() {
     let mut path = PathBuf::from("to_hide/some.file");
@@ -113,3 +116,75 @@ fn into_unhidden() {
     assert!(!unhidden_path2.as_ref().unwrap().exists());
     assert!(!unhidden_path2.as_ref().unwrap().is_hidden().unwrap());
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn dot_components_are_not_hidden() {
+    use areia::{Hidden, is_hidden_name};
+    use std::ffi::OsStr;
+    use std::path::PathBuf;
+
+    assert!(!PathBuf::from("./notes.txt").is_hidden().unwrap());
+    assert!(!PathBuf::from("../shared/file").is_hidden().unwrap());
+    assert!(!PathBuf::from("a/.cache/../file").is_hidden().unwrap());
+    assert!(PathBuf::from("../.cache/file").is_hidden().unwrap());
+    assert!(is_hidden_name(OsStr::new(".bashrc")));
+    assert!(!is_hidden_name(OsStr::new("..")));
+
+    let unhidden_path = PathBuf::from("./unreal_dot/.some.file")
+        .try_into_unhidden_path()
+        .unwrap();
+    assert_eq!(unhidden_path, PathBuf::from("./unreal_dot/some.file"));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn unhide_normalized_components() {
+    use areia::Hidden;
+    use std::path::PathBuf;
+
+    let tmp = common::TempDir::new("unhide");
+    let dir = tmp.path();
+    std::fs::create_dir(dir.join(".outer")).unwrap();
+    std::fs::File::create(dir.join(".outer/.inner.file")).unwrap();
+
+    // `.skipped` is removed by the normalization, so it is neither required nor renamed
+    let mut hidden_path = dir.join(".skipped/../.outer/.inner.file");
+    let unhidden_path = hidden_path.unhide().unwrap();
+    assert_eq!(unhidden_path, dir.join("outer/inner.file"));
+    assert!(unhidden_path.is_file());
+    assert!(!unhidden_path.is_hidden().unwrap());
+    assert!(!dir.join(".outer").exists());
+
+    assert_eq!(
+        PathBuf::from("a/.b/../.c")
+            .try_into_unhidden_path()
+            .unwrap(),
+        PathBuf::from("a/c")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn hide_non_utf8_name() {
+    use areia::Hidden;
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    let tmp = common::TempDir::new("non-utf8");
+    let dir = tmp.path();
+    // "caf\xe9.txt" in Latin-1
+    let mut path = dir.join(OsStr::from_bytes(b"caf\xe9.txt"));
+    let mut hidden_path = path.hide().unwrap();
+    assert_eq!(hidden_path, dir.join(OsStr::from_bytes(b".caf\xe9.txt")));
+    assert!(hidden_path.is_file());
+    assert!(hidden_path.is_hidden().unwrap());
+
+    let unhidden_path = hidden_path.unhide().unwrap();
+    assert_eq!(unhidden_path, path);
+    assert!(unhidden_path.is_file());
+    assert!(!unhidden_path.is_hidden().unwrap());
+
+    assert!(PathBuf::from("a/..").try_into_hidden_path().is_err());
+}
@@ -0,0 +1,128 @@
+use areia::{BaseDirs, ProjectDirs, error::AreiaError};
+use std::path::PathBuf;
+
+#[test]
+fn create_new_project() {
+    let new_project = ProjectDirs::new("com", "Foo Corp", "Bar App");
+    assert!(new_project.is_ok());
+}
+
+#[test]
+fn empty_application_is_rejected() {
+    let base = BaseDirs::new().unwrap();
+    assert!(ProjectDirs::from_base_dirs(&base, "com", "Foo Corp", "").is_err());
+    assert!(ProjectDirs::from_base_dirs(&base, "com", "Foo Corp", "   ").is_err());
+}
+
+#[test]
+fn names_are_trimmed() {
+    let base = BaseDirs::new().unwrap();
+    let padded = ProjectDirs::from_base_dirs(&base, " com ", " Foo Corp ", " Bar App ").unwrap();
+    let plain = ProjectDirs::from_base_dirs(&base, "com", "Foo Corp", "Bar App").unwrap();
+    assert_eq!(padded.project_path(), plain.project_path());
+    assert_eq!(padded.config_dir(), plain.config_dir());
+}
+
+#[test]
+fn names_must_be_single_components() {
+    let base = BaseDirs::new().unwrap();
+    for application in ["../evil", "a/b", "a\\b", "..", "."] {
+        assert!(matches!(
+            ProjectDirs::from_base_dirs(&base, "com", "Foo Corp", application),
+            Err(AreiaError::InvalidProjectName(_))
+        ));
+    }
+    assert!(matches!(
+        ProjectDirs::from_base_dirs(&base, "com", "Foo/Corp", "Bar App"),
+        Err(AreiaError::InvalidProjectName(_))
+    ));
+    assert!(matches!(
+        ProjectDirs::from_base_dirs(&base, "..", "Foo Corp", "Bar App"),
+        Err(AreiaError::InvalidProjectName(_))
+    ));
+}
+
+#[test]
+fn all_paths_inside_base_dirs() {
+    let base = BaseDirs::new().unwrap();
+    let project = ProjectDirs::from_base_dirs(&base, "com", "Foo Corp", "Bar App").unwrap();
+    let project_path = project.project_path();
+
+    assert!(
+        project
+            .cache_dir()
+            .starts_with(base.cache_dir().join(project_path))
+    );
+    assert!(
+        project
+            .config_dir()
+            .starts_with(base.config_dir().join(project_path))
+    );
+    assert!(
+        project
+            .config_local_dir()
+            .starts_with(base.config_local_dir().join(project_path))
+    );
+    assert!(
+        project
+            .data_dir()
+            .starts_with(base.data_dir().join(project_path))
+    );
+    assert!(
+        project
+            .data_local_dir()
+            .starts_with(base.data_local_dir().join(project_path))
+    );
+    assert!(
+        project
+            .preference_dir()
+            .starts_with(base.preference_dir().join(project_path))
+    );
+    assert_eq!(
+        project.runtime_dir().is_some(),
+        base.runtime_dir().is_some()
+    );
+    assert_eq!(project.state_dir().is_some(), base.state_dir().is_some());
+}
+
+#[test]
+fn cache_is_apart_from_config_and_data() {
+    let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    for dir in [
+        project.config_dir(),
+        project.config_local_dir(),
+        project.data_dir(),
+        project.data_local_dir(),
+    ] {
+        assert!(!dir.starts_with(project.cache_dir()));
+        assert!(!project.cache_dir().starts_with(dir));
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn project_path_naming() {
+    let project = ProjectDirs::new("com", "Foo Corp", "  My Cool   App ").unwrap();
+    assert_eq!(project.project_path(), &PathBuf::from("my-cool-app"));
+}
+
+#[test]
+#[cfg(target_os = "macos")]
+fn project_path_naming() {
+    let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    assert_eq!(
+        project.project_path(),
+        &PathBuf::from("com.Foo-Corp.Bar-App")
+    );
+    let project = ProjectDirs::new("", "", "Bar App").unwrap();
+    assert_eq!(project.project_path(), &PathBuf::from("Bar-App"));
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn project_path_naming() {
+    let project = ProjectDirs::new("com", "Foo Corp", "Bar App").unwrap();
+    assert_eq!(project.project_path(), &PathBuf::from("Foo Corp\\Bar App"));
+    let project = ProjectDirs::new("com", "", "Bar App").unwrap();
+    assert_eq!(project.project_path(), &PathBuf::from("Bar App"));
+}
@@ -1,9 +1,11 @@
-#![cfg(not(target_os = "linux"))]
+use areia::SuperHidden;
+#[cfg(not(target_os = "linux"))]
+use {areia::BaseDirs, std::path::PathBuf};
 
-use areia::{BaseDirs, SuperHidden};
-use std::fs::{remove_dir, remove_dir_all, remove_file};
-use std::path::PathBuf;
+#[cfg(target_os = "linux")]
+mod common;
 
+#[cfg(not(target_os = "linux"))]
 #[test]
 fn super_hide_non_existent_path() {
     use areia::SuperHidden;
@@ -35,6 +37,7 @@ fn super_hide_non_existent_path() {
     assert!(std::fs::remove_dir_all(unhidden_path.as_ref().unwrap().parent().unwrap()).is_ok());
 }
 
+#[cfg(not(target_os = "linux"))]
 #[test]
 fn super_hide_existing_path() {
     use areia::SuperHidden;
@@ -60,6 +63,7 @@ fn super_hide_existing_path() {
     assert!(std::fs::remove_dir_all(&unhidden_path.parent().unwrap()).is_ok());
 }
 
+#[cfg(not(target_os = "linux"))]
 #[test]
 fn super_hide_already_hidden_path() {
     let base_dirs = BaseDirs::new().unwrap();
@@ -87,3 +91,68 @@ fn super_hide_already_hidden_path() {
     assert!(std::fs::remove_file(super_hidden_path.as_ref().unwrap()).is_ok());
     assert!(std::fs::remove_dir(super_hidden_path.as_ref().unwrap().parent().unwrap()).is_ok());
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn super_hide_keeps_name_and_lists_it() {
+    use std::fs::Permissions;
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = common::TempDir::new("super-hide");
+    let dir = tmp.path();
+    let list = dir.join(".hidden");
+    std::fs::write(&list, "other\nother\n").unwrap();
+    std::fs::set_permissions(&list, Permissions::from_mode(0o640)).unwrap();
+    let mut path = dir.join("Data");
+    std::fs::create_dir(&path).unwrap();
+
+    let super_hidden_path = path.super_hide().unwrap();
+    assert_eq!(super_hidden_path, path);
+    assert!(path.exists());
+    assert!(path.is_super_hidden().unwrap());
+    std::fs::File::create(path.join("inside")).unwrap();
+    assert!(path.join("inside").is_super_hidden().unwrap());
+    assert_eq!(std::fs::read_to_string(&list).unwrap(), "other\nData\n");
+    // The rewritten list keeps its mode
+    assert_eq!(
+        std::fs::metadata(&list).unwrap().permissions().mode() & 0o777,
+        0o640
+    );
+
+    // Hiding again does not duplicate the entry
+    path.super_hide().unwrap();
+    assert_eq!(std::fs::read_to_string(&list).unwrap(), "other\nData\n");
+
+    let unhidden_path = path.super_unhide().unwrap();
+    assert_eq!(unhidden_path, path);
+    assert!(!path.is_super_hidden().unwrap());
+    assert_eq!(std::fs::read_to_string(&list).unwrap(), "other\n");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn super_unhide_removes_empty_list() {
+    let tmp = common::TempDir::new("super-unhide");
+    let dir = tmp.path();
+    std::fs::create_dir(dir.join("outer")).unwrap();
+    let mut path = dir.join("outer").join("notes.txt");
+    std::fs::File::create(&path).unwrap();
+    let mut outer = dir.join("outer");
+
+    outer.super_hide().unwrap();
+    assert!(path.is_super_hidden().unwrap());
+    // Hidden through its parent, but listed itself as well so it stays hidden without it
+    path.super_hide().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("outer").join(".hidden")).unwrap(),
+        "notes.txt\n"
+    );
+
+    path.super_unhide().unwrap();
+    assert!(!path.is_super_hidden().unwrap());
+    assert!(!dir.join(".hidden").exists());
+    assert!(!dir.join("outer").join(".hidden").exists());
+
+    let mut missing = dir.join("missing");
+    assert!(missing.super_hide().is_err());
+}
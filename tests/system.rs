@@ -0,0 +1,54 @@
+use areia::SystemDirs;
+use std::path::PathBuf;
+
+#[test]
+fn create_new_system() {
+    let new_system = SystemDirs::new();
+    assert!(new_system.is_ok());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn all_paths_present() {
+    let system = SystemDirs::new().unwrap();
+
+    assert_eq!(system.root_dir(), &PathBuf::from("/"));
+    assert!(system.root_dir().is_dir());
+    assert_eq!(system.bin_dir(), Some(&PathBuf::from("/usr/bin")));
+    assert_eq!(
+        system.local_bin_dir(),
+        Some(&PathBuf::from("/usr/local/bin"))
+    );
+    assert_eq!(system.data_dir(), Some(&PathBuf::from("/var/lib")));
+    assert_eq!(system.share_dir(), Some(&PathBuf::from("/usr/share")));
+    assert_eq!(system.config_dir(), Some(&PathBuf::from("/etc")));
+    assert!(system.config_dir().unwrap().is_dir());
+}
+
+#[test]
+#[cfg(target_os = "macos")]
+fn all_paths_present() {
+    let system = SystemDirs::new().unwrap();
+
+    assert_eq!(system.root_dir(), &PathBuf::from("/"));
+    assert!(system.bin_dir().unwrap().is_dir());
+    assert!(system.local_bin_dir().is_some());
+    assert!(system.data_dir().unwrap().is_dir());
+    assert!(system.share_dir().unwrap().is_dir());
+    assert!(system.config_dir().unwrap().is_dir());
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn all_paths_present() {
+    let system = SystemDirs::new().unwrap();
+
+    assert!(system.root_dir().is_absolute());
+    assert!(system.root_dir().is_dir());
+    assert!(system.bin_dir().is_none());
+    assert!(system.local_bin_dir().unwrap().is_dir());
+    assert!(system.data_dir().unwrap().is_dir());
+    assert!(system.data_dir().unwrap().starts_with(system.root_dir()));
+    assert!(system.share_dir().is_none());
+    assert!(system.config_dir().is_none());
+}
@@ -0,0 +1,136 @@
+use areia::{BaseDirs, DirKind, MapEnv, PathTokens, UserDirs, error::AreiaError};
+use std::path::{Path, PathBuf};
+
+fn tokens_for(home: &str) -> PathTokens {
+    let env = MapEnv::new(home).with_var("XDG_RUNTIME_DIR", "/run/user/1000");
+    PathTokens::from_dirs(
+        &BaseDirs::from_env(&env).unwrap(),
+        &UserDirs::from_env(&env).unwrap(),
+    )
+}
+
+#[test]
+fn round_trip_tokens() {
+    let tokens = PathTokens::new().unwrap();
+    let base = BaseDirs::new().unwrap();
+
+    let path = base.data_dir().join("app").join("index");
+    let token = tokens.encode(&path).unwrap();
+    assert_eq!(token, "{data}/app/index");
+    assert_eq!(tokens.resolve(&token).unwrap(), path);
+    assert_eq!(
+        tokens.resolve("{home}").unwrap(),
+        base.home_dir().to_path_buf()
+    );
+    assert_eq!(
+        tokens.resolve("/etc/hosts").unwrap(),
+        PathBuf::from("/etc/hosts")
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn tokens_move_between_accounts() {
+    let alice = tokens_for("/home/alice");
+    let bob = tokens_for("/home/bob");
+
+    let token = alice
+        .encode("/home/alice/.local/share/app/db.sqlite")
+        .unwrap();
+    assert_eq!(token, "{data}/app/db.sqlite");
+    assert_eq!(
+        bob.resolve(&token).unwrap(),
+        PathBuf::from("/home/bob/.local/share/app/db.sqlite")
+    );
+
+    // The most specific directory wins over the home directory
+    let (kind, rest) = alice.classify("/home/alice/Downloads/foo.zip").unwrap();
+    assert_eq!(kind, DirKind::Download);
+    assert_eq!(rest, Path::new("foo.zip"));
+    assert_eq!(
+        alice.encode("/home/alice/notes.md").as_deref(),
+        Some("{home}/notes.md")
+    );
+    // Of equally specific directories, `config` beats `config-local` and `preference`
+    assert_eq!(
+        alice.classify("/home/alice/.config").map(|(kind, _)| kind),
+        Some(DirKind::Config)
+    );
+    assert_eq!(
+        alice.encode("/run/user/1000/app.sock").as_deref(),
+        Some("{runtime}/app.sock")
+    );
+    assert!(alice.classify("/home/alicia/notes.md").is_none());
+    assert!(alice.encode("/etc/hosts").is_none());
+}
+
+#[test]
+fn reject_invalid_tokens() {
+    let tokens = tokens_for("/home/alice");
+    assert!(matches!(
+        tokens.resolve("{nope}/x"),
+        Err(AreiaError::UnknownDirKind(name)) if name == "nope"
+    ));
+    assert!(matches!(
+        tokens.resolve("{data}/../../etc/passwd"),
+        Err(AreiaError::InvalidToken(_))
+    ));
+    assert!(matches!(
+        tokens.resolve("{data}foo"),
+        Err(AreiaError::InvalidToken(_))
+    ));
+    // `~/.local/bin` does not exist for alice
+    assert!(matches!(
+        tokens.resolve("{executable}/x"),
+        Err(AreiaError::DirectoryNotAvailable(DirKind::Executable))
+    ));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn tokens_move_between_operating_systems() {
+    // The layout of `BaseDirs` on Windows, where several kinds share a directory
+    let local = PathBuf::from("/c/Users/alice/AppData/Local");
+    let roaming = PathBuf::from("/c/Users/alice/AppData/Roaming");
+    let windows: PathTokens = [
+        (DirKind::Home, PathBuf::from("/c/Users/alice")),
+        (DirKind::Cache, local.clone()),
+        (DirKind::Config, roaming.clone()),
+        (DirKind::ConfigLocal, local.clone()),
+        (DirKind::Data, roaming.clone()),
+        (DirKind::DataLocal, local.clone()),
+        (DirKind::Preference, roaming.clone()),
+    ]
+    .into_iter()
+    .collect();
+    let linux = tokens_for("/home/alice");
+
+    let token = windows.encode(roaming.join("app/db.sqlite")).unwrap();
+    assert_eq!(token, "{data}/app/db.sqlite");
+    assert_eq!(
+        linux.resolve(&token).unwrap(),
+        PathBuf::from("/home/alice/.local/share/app/db.sqlite")
+    );
+
+    let token = windows.encode(local.join("app/state.bin")).unwrap();
+    assert_eq!(token, "{data-local}/app/state.bin");
+    assert_eq!(
+        linux.resolve(&token).unwrap(),
+        PathBuf::from("/home/alice/.local/share/app/state.bin")
+    );
+
+    // The caller knows it is a cache
+    let token = windows
+        .encode_as(DirKind::Cache, local.join("app/index"))
+        .unwrap();
+    assert_eq!(token, "{cache}/app/index");
+    assert_eq!(
+        linux.resolve(&token).unwrap(),
+        PathBuf::from("/home/alice/.cache/app/index")
+    );
+    assert!(
+        windows
+            .encode_as(DirKind::Cache, roaming.join("app"))
+            .is_none()
+    );
+}
@@ -1,7 +1,13 @@
 use areia::UserDirs;
+#[cfg(target_os = "linux")]
+use areia::{DirKind, DirSource, MapEnv, error::AreiaError};
+#[cfg(target_os = "linux")]
+use std::{fs, path::PathBuf};
+
+#[cfg(target_os = "linux")]
+mod common;
 
 #[test]
-#[ignore = "CI does not have a `user-dirs.dirs` file"]
 fn create_new_user() {
     let new_user = UserDirs::new();
     assert!(new_user.is_ok());
@@ -80,6 +86,8 @@ fn all_paths_present() {
     assert!(user.template_dir().is_none());
     assert!(user.video_dir().is_some());
     assert!(user.video_dir().as_ref().unwrap().is_dir());
+    // No convention on macOS
+    assert!(user.project_dir().is_none());
 }
 
 #[test]
@@ -107,4 +115,253 @@ fn all_paths_present() {
     assert!(user.template_dir().is_none());
     assert!(user.video_dir().is_some());
     assert!(user.video_dir().as_ref().unwrap().is_dir());
+    assert_eq!(
+        user.project_dir(),
+        &Some(user.home_dir().join("source").join("repos"))
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn missing_user_dirs_file_falls_back() {
+    let tmp = common::TempDir::new("user-fallback");
+    let home = tmp.path();
+    let user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+
+    match user.source(DirKind::Desktop) {
+        Some(DirSource::Default) => {
+            assert_eq!(user.desktop_dir(), &Some(home.join("Desktop")));
+        }
+        Some(DirSource::SystemDefaults(file)) => {
+            assert!(file.ends_with("user-dirs.defaults"));
+        }
+        other => panic!("Unexpected source {other:?}"),
+    }
+    assert_eq!(user.source(DirKind::Font), Some(&DirSource::Default));
+    assert_eq!(user.project_dir(), &Some(home.join("Projects")));
+    assert_eq!(user.source(DirKind::Project), Some(&DirSource::Default));
+    assert!(user.source(DirKind::Cache).is_none());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn user_dirs_file_is_read() {
+    let tmp = common::TempDir::new("user-file");
+    let home = tmp.path();
+    let file = home.join(".config").join("user-dirs.dirs");
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(
+        &file,
+        "# Written by xdg-user-dirs-update\nXDG_DESKTOP_DIR=\"$HOME/Schreibtisch\"\n",
+    )
+    .unwrap();
+    let user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+
+    assert_eq!(user.desktop_dir(), &Some(home.join("Schreibtisch")));
+    assert_eq!(
+        user.source(DirKind::Desktop),
+        Some(&DirSource::UserDirsFile(file))
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn user_dirs_file_follows_config_home() {
+    let tmp = common::TempDir::new("user-config-home");
+    let home = tmp.path();
+    let config = home.join("relocated");
+    fs::create_dir_all(&config).unwrap();
+    fs::write(
+        config.join("user-dirs.dirs"),
+        "XDG_DOWNLOAD_DIR=\"$HOME/Incoming\"\n",
+    )
+    .unwrap();
+    let env = MapEnv::new(home).with_var("XDG_CONFIG_HOME", &config);
+    let user = UserDirs::from_env(&env).unwrap();
+
+    assert_eq!(user.download_dir(), &Some(home.join("Incoming")));
+    assert_eq!(
+        user.source(DirKind::Download),
+        Some(&DirSource::UserDirsFile(config.join("user-dirs.dirs")))
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn system_defaults_follow_config_dirs() {
+    let tmp = common::TempDir::new("user-config-dirs");
+    let home = tmp.path();
+    let empty = home.join("empty");
+    let xdg = home.join("xdg");
+    fs::create_dir_all(&empty).unwrap();
+    fs::create_dir_all(&xdg).unwrap();
+    fs::write(
+        xdg.join("user-dirs.defaults"),
+        "# Default settings for user directories\nDESKTOP=Arbeitsfläche\nTEMPLATES=\n",
+    )
+    .unwrap();
+    let dirs = std::env::join_paths([&empty, &xdg]).unwrap();
+    let env = MapEnv::new(home).with_var("XDG_CONFIG_DIRS", dirs);
+    let user = UserDirs::from_env(&env).unwrap();
+
+    let defaults = DirSource::SystemDefaults(xdg.join("user-dirs.defaults"));
+    assert_eq!(user.desktop_dir(), &Some(home.join("Arbeitsfläche")));
+    assert_eq!(user.source(DirKind::Desktop), Some(&defaults));
+    assert!(user.template_dir().is_none());
+    assert_eq!(user.source(DirKind::Template), Some(&defaults));
+    assert_eq!(user.video_dir(), &Some(home.join("Videos")));
+    assert_eq!(user.source(DirKind::Video), Some(&DirSource::Default));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_dir_rewrites_user_dirs_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = common::TempDir::new("user-set-dir");
+    let home = tmp.path();
+    let file = home.join(".config").join("user-dirs.dirs");
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(
+        &file,
+        "# Keep me\nXDG_DOWNLOAD_DIR=\"$HOME/Downloads\"\nXDG_MUSIC_DIR=\"/srv/music\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+    let mut user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+
+    let download = home.join("My \"$pecial\" `Files`\\");
+    user.set_dir(DirKind::Download, &download).unwrap();
+    let desktop = PathBuf::from("/mnt/desk top");
+    user.set_dir(DirKind::Desktop, &desktop).unwrap();
+    assert_eq!(user.download_dir(), &Some(download.clone()));
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "# Keep me\n\
+         XDG_DOWNLOAD_DIR=\"$HOME/My \\\"\\$pecial\\\" \\`Files\\`\\\\\"\n\
+         XDG_MUSIC_DIR=\"/srv/music\"\n\
+         XDG_DESKTOP_DIR=\"/mnt/desk top\"\n"
+    );
+    assert_eq!(
+        fs::metadata(&file).unwrap().permissions().mode() & 0o777,
+        0o640
+    );
+
+    // Reading the file again round-trips the escaped paths
+    let user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+    assert_eq!(user.download_dir(), &Some(download));
+    assert_eq!(user.desktop_dir(), &Some(desktop));
+    assert_eq!(user.audio_dir(), &Some(PathBuf::from("/srv/music")));
+
+    assert!(user.clone().set_dir(DirKind::Font, "/fonts").is_err());
+    assert!(user.clone().set_dir(DirKind::Video, "Videos").is_err());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_dir_rejects_line_breaks() {
+    let tmp = common::TempDir::new("user-set-dir-line-break");
+    let home = tmp.path();
+    let file = home.join(".config").join("user-dirs.dirs");
+    let mut user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+
+    for name in ["Down\"\nXDG_DESKTOP_DIR=\"/tmp", "Down\rloads"] {
+        assert!(matches!(
+            user.set_dir(DirKind::Download, home.join(name)),
+            Err(AreiaError::WritingUserDirsNotSupported(_))
+        ));
+    }
+    assert!(!file.exists());
+    assert_ne!(user.download_dir(), &Some(home.join("Down\rloads")));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn bootstrap_creates_dirs_and_file() {
+    let tmp = common::TempDir::new("user-bootstrap");
+    let home = tmp.path();
+    let mut user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+    user.bootstrap().unwrap();
+
+    let file = home.join(".config").join("user-dirs.dirs");
+    for kind in [DirKind::Desktop, DirKind::Download, DirKind::Video] {
+        assert_eq!(
+            user.source(kind),
+            Some(&DirSource::UserDirsFile(file.clone()))
+        );
+    }
+    if let Some(desktop) = user.desktop_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        assert!(desktop.is_dir());
+        assert_eq!(
+            fs::metadata(desktop).unwrap().permissions().mode() & 0o777,
+            0o700
+        );
+    }
+    let reread = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+    assert_eq!(reread.desktop_dir(), user.desktop_dir());
+    assert_eq!(reread.template_dir(), user.template_dir());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn custom_user_dirs() {
+    let tmp = common::TempDir::new("user-custom");
+    let home = tmp.path();
+    let file = home.join(".config").join("user-dirs.dirs");
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(
+        &file,
+        "XDG_PICTURES_DIR=\"$HOME/Bilder\"\n\
+         XDG_DOWNLOAD_DIR=\"$HOME/Downloads\"\n\
+         XDG_PROJECTS_DIR=\"$HOME/Code\"\n\
+         XDG_GAMES_DIR=\"/srv/games\"\n\
+         XDG_DISABLED_DIR=\"$HOME/\"\n",
+    )
+    .unwrap();
+    let user = UserDirs::from_env(&MapEnv::new(home)).unwrap();
+
+    assert_eq!(user.project_dir(), &Some(home.join("Code")));
+    assert_eq!(
+        user.source(DirKind::Project),
+        Some(&DirSource::UserDirsFile(file.clone()))
+    );
+    // Without an entry, screenshots default to a sub directory of the pictures
+    assert_eq!(
+        user.screenshot_dir(),
+        &Some(home.join("Bilder").join("Screenshots"))
+    );
+    assert_eq!(user.source(DirKind::Screenshot), Some(&DirSource::Default));
+
+    assert_eq!(
+        user.custom("GAMES"),
+        Some(PathBuf::from("/srv/games").as_path())
+    );
+    assert_eq!(user.custom("XDG_GAMES_DIR"), user.custom("GAMES"));
+    assert_eq!(
+        user.custom("DOWNLOAD"),
+        Some(home.join("Downloads").as_path())
+    );
+    assert!(user.custom("DISABLED").is_none());
+    assert!(user.custom("MISSING").is_none());
+
+    let keys = user.xdg_dirs().map(|(key, _)| key).collect::<Vec<_>>();
+    for key in ["PICTURES", "DOWNLOAD", "SCREENSHOTS", "PROJECTS", "GAMES"] {
+        assert!(keys.contains(&key), "{key} is missing in {keys:?}");
+    }
+    assert!(!keys.contains(&"DESKTOP"));
+}
+
+#[test]
+fn get_by_kind() {
+    use areia::DirKind;
+
+    let user = UserDirs::new().unwrap();
+    assert_eq!(user.get(DirKind::Home), Some(user.home_dir().as_path()));
+    assert_eq!(user.get(DirKind::Video), user.video_dir().as_deref());
+    assert!(user.get(DirKind::Config).is_none());
+    for (kind, path) in user.iter() {
+        assert_eq!(user.get(kind), Some(path));
+    }
 }
@@ -0,0 +1,69 @@
+#![cfg(target_os = "linux")]
+
+use areia::{WatchEvent, Watcher};
+use std::{sync::mpsc::Receiver, time::Duration};
+
+mod common;
+
+fn next(events: &Receiver<WatchEvent>) -> WatchEvent {
+    events.recv_timeout(Duration::from_secs(5)).unwrap()
+}
+
+#[test]
+fn watch_directory() {
+    let tmp = common::TempDir::new("watch-dir");
+    let dir = tmp.path();
+    let (mut watcher, events) = Watcher::channel().unwrap();
+    watcher.watch(dir).unwrap();
+
+    let file = dir.join("settings.toml");
+    std::fs::write(&file, "a = 1").unwrap();
+    assert_eq!(next(&events), WatchEvent::Created(file.clone()));
+    assert_eq!(next(&events), WatchEvent::Modified(file.clone()));
+
+    let moved = dir.join("moved.toml");
+    std::fs::rename(&file, &moved).unwrap();
+    assert_eq!(next(&events), WatchEvent::MovedFrom(file.clone()));
+    assert_eq!(next(&events), WatchEvent::MovedTo(moved.clone()));
+
+    // The whole directory stays watched
+    watcher.unwatch(&moved).unwrap();
+    std::fs::remove_file(&moved).unwrap();
+    assert_eq!(next(&events), WatchEvent::Deleted(moved.clone()));
+    assert_eq!(
+        WatchEvent::Deleted(moved.clone()).path(),
+        Some(moved.as_path())
+    );
+
+    watcher.unwatch(dir).unwrap();
+    std::fs::write(&file, "a = 2").unwrap();
+    assert!(events.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn watch_file_across_replacement() {
+    let tmp = common::TempDir::new("watch-file");
+    let dir = tmp.path();
+    let file = dir.join("user-dirs.dirs");
+    let (mut watcher, events) = Watcher::channel().unwrap();
+    // The file does not exist yet, only its directory has to
+    watcher.watch(&file).unwrap();
+
+    std::fs::write(dir.join("unrelated"), "").unwrap();
+    let replacement = dir.join("user-dirs.dirs.tmp");
+    std::fs::write(&replacement, "XDG_DESKTOP_DIR=\"$HOME/Desk\"").unwrap();
+    std::fs::rename(&replacement, &file).unwrap();
+    assert_eq!(next(&events), WatchEvent::MovedTo(file.clone()));
+
+    std::fs::write(&file, "").unwrap();
+    assert_eq!(next(&events), WatchEvent::Modified(file.clone()));
+
+    drop(watcher);
+    assert!(events.recv().is_err());
+}
+
+#[test]
+fn watch_missing_directory() {
+    let (mut watcher, _events) = Watcher::channel().unwrap();
+    assert!(watcher.watch("/areia/does/not/exist/file").is_err());
+}